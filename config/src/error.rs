@@ -0,0 +1,66 @@
+//! The error type shared by the config loaders and validators.
+
+use crate::operator::SignerType;
+use std::path::PathBuf;
+use types::OperatorError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid YAML config: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("invalid TOML config: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("{0} is not a .toml, .yaml or .yml file")]
+    UnsupportedFormat(PathBuf),
+    #[error("invalid config: {0}")]
+    Invalid(serde_json::Error),
+    #[error("invalid config value {key}: {source}")]
+    InvalidValue {
+        key: String,
+        source: serde_json::Error,
+    },
+    #[error("override {0:?} is not of the form key=value")]
+    InvalidOverride(String),
+    #[error("environment variable {0} is not valid UTF-8")]
+    NonUnicodeEnv(String),
+    #[error("{0} must not be the zero address")]
+    ZeroAddress(&'static str),
+    #[error("invalid operator: {0}")]
+    Operator(#[from] OperatorError),
+    #[error("{field} is not a valid URL: {reason}")]
+    InvalidUrl { field: &'static str, reason: String },
+    #[error("signer_type {0} is not supported")]
+    UnsupportedSigner(SignerType),
+    #[error("keystore {0} does not exist")]
+    KeystoreNotFound(PathBuf),
+    #[error("eth_rpc_url is unreachable: {0}")]
+    Rpc(#[from] reqwest::Error),
+    #[error("eth_rpc_url returned an invalid chain id: {0}")]
+    InvalidRpcResponse(String),
+    #[error("eth_rpc_url is on chain {actual}, config expects chain {expected}")]
+    ChainIdMismatch { expected: u64, actual: u64 },
+}
+
+errors::error_codes!(ConfigError {
+    Io => ("config.io", Io),
+    Yaml => ("config.parse", Validation),
+    Toml => ("config.parse", Validation),
+    UnsupportedFormat => ("config.unsupported_format", Validation),
+    Invalid => ("config.invalid_value", Validation),
+    InvalidValue => ("config.invalid_value", Validation),
+    InvalidOverride => ("config.invalid_override", Validation),
+    NonUnicodeEnv => ("config.invalid_env", Validation),
+    ZeroAddress => ("config.zero_address", Validation),
+    Operator => ("config.invalid_operator", Validation),
+    InvalidUrl => ("config.invalid_url", Validation),
+    UnsupportedSigner => ("config.unsupported_signer", Validation),
+    KeystoreNotFound => ("config.keystore_not_found", Validation),
+    Rpc => ("config.rpc_unreachable", Rpc),
+    InvalidRpcResponse => ("config.invalid_rpc_response", Rpc),
+    ChainIdMismatch => ("config.chain_id_mismatch", Validation),
+});
@@ -0,0 +1,210 @@
+//! Configuration merged from defaults, files, environment variables and
+//! command-line overrides, so binaries don't each roll their own parsing.
+//!
+//! Layers always apply in that order, whatever order they're added in:
+//!
+//! ```no_run
+//! # #[derive(serde::Serialize, serde::Deserialize, Default)]
+//! # struct Settings { addr: String }
+//! let settings: Settings = config::ConfigLoader::new()
+//!     .with_defaults(&Settings::default())?
+//!     .with_file("node.toml")
+//!     .with_env("EIGEN_NODE")
+//!     .with_override("addr", "0.0.0.0:9010")
+//!     .load()?
+//!     .extract()?;
+//! # Ok::<(), config::ConfigError>(())
+//! ```
+
+use crate::ConfigError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    defaults: Value,
+    files: Vec<PathBuf>,
+    env_prefix: Option<String>,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_defaults<T: Serialize>(mut self, defaults: &T) -> Result<Self, ConfigError> {
+        self.defaults = serde_json::to_value(defaults).map_err(ConfigError::Invalid)?;
+        Ok(self)
+    }
+
+    /// A `.toml`, `.yaml` or `.yml` file. Later files override earlier ones.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Reads `<prefix>_<KEY>` variables. `__` separates nested keys, so
+    /// `EIGEN_NODE_METRICS__ADDR` sets `metrics.addr`.
+    pub fn with_env(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets a dotted `key` such as `"metrics.addr"`, e.g. from a CLI flag.
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// Adds a `key=value` override, as passed to a `--set` flag.
+    pub fn with_override_arg(self, arg: &str) -> Result<Self, ConfigError> {
+        match arg.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok(self.with_override(key, value)),
+            _ => Err(ConfigError::InvalidOverride(arg.to_string())),
+        }
+    }
+
+    pub fn load(&self) -> Result<LayeredConfig, ConfigError> {
+        let mut root = match &self.defaults {
+            Value::Null => Value::Object(Map::new()),
+            defaults => defaults.clone(),
+        };
+        for path in &self.files {
+            merge(&mut root, read_file(path)?);
+        }
+        if let Some(prefix) = &self.env_prefix {
+            let prefix = format!("{prefix}_");
+            let mut vars = vec![];
+            // Other variables may hold anything, so only ours must be UTF-8.
+            for (name, value) in std::env::vars_os() {
+                let Some(key) = name.to_str().and_then(|name| name.strip_prefix(&prefix)) else {
+                    continue;
+                };
+                let value = value
+                    .into_string()
+                    .map_err(|_| ConfigError::NonUnicodeEnv(name.to_string_lossy().into_owned()))?;
+                vars.push((key.to_lowercase().replace("__", "."), value));
+            }
+            // Deterministic order when two variables map to overlapping keys.
+            vars.sort();
+            for (key, value) in vars {
+                set(&mut root, &key, value);
+            }
+        }
+        for (key, value) in &self.overrides {
+            set(&mut root, key, value.clone());
+        }
+        Ok(LayeredConfig(root))
+    }
+}
+
+/// The merged configuration tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayeredConfig(Value);
+
+impl LayeredConfig {
+    pub fn extract<T: DeserializeOwned>(&self) -> Result<T, ConfigError> {
+        T::deserialize(&self.0).map_err(ConfigError::Invalid)
+    }
+
+    /// The value at a dotted `key`, or `None` if no layer set it.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ConfigError> {
+        let Some(value) = key
+            .split('.')
+            .try_fold(&self.0, |value, part| value.get(part))
+        else {
+            return Ok(None);
+        };
+        T::deserialize(value)
+            .map(Some)
+            .map_err(|source| ConfigError::InvalidValue {
+                key: key.to_string(),
+                source,
+            })
+    }
+}
+
+fn read_file(path: &Path) -> Result<Value, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => Ok(toml::from_str(&contents)?),
+        Some("yaml" | "yml") => Ok(serde_yaml::from_str(&contents)?),
+        _ => Err(ConfigError::UnsupportedFormat(path.to_path_buf())),
+    }
+}
+
+fn merge(base: &mut Value, layer: Value) {
+    match (base, layer) {
+        (Value::Object(base), Value::Object(layer)) => {
+            for (key, value) in layer {
+                merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+/// Sets a string from the environment or command line, typed after the value
+/// it replaces so `"9010"` stays a string for string fields and becomes a
+/// number for numeric ones. See [`coerce`].
+fn set(root: &mut Value, key: &str, raw: String) {
+    let mut node = root;
+    for part in key.split('.') {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        node = node
+            .as_object_mut()
+            .unwrap()
+            .entry(part)
+            .or_insert(Value::Null);
+    }
+    *node = coerce(node, raw);
+}
+
+/// Types `raw` after `template`, the value it replaces. Values that don't
+/// parse as the template's type stay strings.
+///
+/// Values replacing `null` (an unset `Option`) or a key no layer set are read
+/// as a JSON bool, number, `null` or quoted string, falling back to a plain
+/// string. A numeric `Option<String>` therefore needs quotes, as in `"123"`.
+///
+/// Arrays take comma-separated items, each typed after the array's first
+/// element. Items replacing an empty array stay strings unless written as a
+/// JSON array such as `[0,1]`.
+fn coerce(template: &Value, raw: String) -> Value {
+    match template {
+        Value::Bool(_) => raw.parse().map(Value::Bool).unwrap_or(Value::String(raw)),
+        Value::Number(_) => serde_json::from_str::<serde_json::Number>(&raw)
+            .map(Value::Number)
+            .unwrap_or(Value::String(raw)),
+        Value::Array(items) => {
+            if let Ok(Value::Array(items)) = serde_json::from_str(&raw) {
+                return Value::Array(items);
+            }
+            if raw.trim().is_empty() {
+                return Value::Array(vec![]);
+            }
+            let string = Value::String(String::new());
+            let item = items.first().unwrap_or(&string);
+            Value::Array(
+                raw.split(',')
+                    .map(|raw| coerce(item, raw.trim().to_string()))
+                    .collect(),
+            )
+        }
+        Value::Null => match serde_json::from_str(&raw) {
+            Ok(scalar @ (Value::Bool(_) | Value::Number(_) | Value::Null | Value::String(_))) => {
+                scalar
+            }
+            _ => Value::String(raw),
+        },
+        Value::String(_) | Value::Object(_) => Value::String(raw),
+    }
+}
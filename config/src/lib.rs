@@ -0,0 +1,9 @@
+pub mod error;
+pub mod layered;
+pub mod metadata;
+pub mod operator;
+
+pub use error::ConfigError;
+pub use layered::{ConfigLoader, LayeredConfig};
+pub use metadata::{MetadataError, OperatorMetadata};
+pub use operator::{AddressError, OperatorConfig};
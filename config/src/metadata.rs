@@ -0,0 +1,211 @@
+//! The operator metadata JSON an operator's `metadata_url` points to, with
+//! the validation rules the EigenLayer UI applies to it (mirroring
+//! eigensdk-go's `OperatorMetadata.Validate`). Check a URI before committing
+//! it on-chain with [`fetch_and_validate`].
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use types::operator::validate_public_url;
+use url::Url;
+
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+pub const MAX_TWITTER_HANDLE_LEN: usize = 15;
+/// Largest metadata document or logo that is downloaded.
+pub const MAX_CONTENT_LEN: usize = 1024 * 1024;
+
+/// Punctuation allowed in names and descriptions besides letters, digits and
+/// spaces.
+const TEXT_PUNCTUATION: &str = "+.,;:?!'\"-_/()[]~&#$—%";
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataError {
+    #[error("{0} is required")]
+    Missing(&'static str),
+    #[error("{field} is longer than {max} characters")]
+    TooLong { field: &'static str, max: usize },
+    #[error("{0} may only contain letters, digits, spaces and {TEXT_PUNCTUATION}")]
+    InvalidText(&'static str),
+    #[error("{field} is not a valid URL: {reason}")]
+    InvalidUrl { field: &'static str, reason: String },
+    #[error("twitter must be a https://x.com/<handle> or https://twitter.com/<handle> URL with a handle of at most {MAX_TWITTER_HANDLE_LEN} characters, got {0}")]
+    InvalidTwitter(String),
+    #[error("failed to fetch {url}: {source}")]
+    Fetch { url: String, source: reqwest::Error },
+    #[error("{url} returned HTTP {status}")]
+    HttpStatus {
+        url: String,
+        status: reqwest::StatusCode,
+    },
+    #[error("{url} is larger than {MAX_CONTENT_LEN} bytes")]
+    TooLarge { url: String },
+    #[error("metadata is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("logo must be a PNG image, got content type {0:?}")]
+    LogoNotPng(String),
+}
+
+errors::error_codes!(MetadataError {
+    Missing => ("config.metadata_missing_field", Validation),
+    TooLong => ("config.metadata_too_long", Validation),
+    InvalidText => ("config.metadata_invalid_text", Validation),
+    InvalidUrl => ("config.metadata_invalid_url", Validation),
+    InvalidTwitter => ("config.metadata_invalid_twitter", Validation),
+    Fetch => ("config.metadata_unreachable", Network),
+    HttpStatus => ("config.metadata_unreachable", Network),
+    TooLarge => ("config.metadata_too_large", Validation),
+    Json => ("config.metadata_parse", Validation),
+    LogoNotPng => ("config.metadata_logo_not_png", Validation),
+});
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorMetadata {
+    pub name: String,
+    #[serde(default)]
+    pub website: String,
+    pub description: String,
+    pub logo: String,
+    #[serde(default)]
+    pub twitter: String,
+}
+
+impl OperatorMetadata {
+    /// Offline checks; the logo itself is checked by [`fetch_and_validate`].
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        validate_text("name", &self.name)?;
+        validate_text("description", &self.description)?;
+        if self.description.chars().count() > MAX_DESCRIPTION_LEN {
+            return Err(MetadataError::TooLong {
+                field: "description",
+                max: MAX_DESCRIPTION_LEN,
+            });
+        }
+        if self.logo.is_empty() {
+            return Err(MetadataError::Missing("logo"));
+        }
+        validate_url("logo", &self.logo)?;
+        if !self.website.is_empty() {
+            validate_url("website", &self.website)?;
+        }
+        if !self.twitter.is_empty() {
+            validate_twitter(&self.twitter)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fetches the metadata at `uri`, validates it, then fetches the logo and
+/// checks it is a PNG.
+pub async fn fetch_and_validate(uri: &str) -> Result<OperatorMetadata, MetadataError> {
+    validate_url("metadata_url", uri)?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|source| MetadataError::Fetch {
+            url: uri.to_string(),
+            source,
+        })?;
+
+    let (_, body) = fetch(&client, uri).await?;
+    let metadata: OperatorMetadata = serde_json::from_slice(&body)?;
+    metadata.validate()?;
+
+    let (content_type, logo) = fetch(&client, &metadata.logo).await?;
+    check_logo(content_type.as_deref(), &logo)?;
+    Ok(metadata)
+}
+
+/// Checks a downloaded logo's content type and PNG signature.
+pub fn check_logo(content_type: Option<&str>, bytes: &[u8]) -> Result<(), MetadataError> {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    let content_type = content_type.unwrap_or_default();
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    if !mime.eq_ignore_ascii_case("image/png") || !bytes.starts_with(PNG_SIGNATURE) {
+        return Err(MetadataError::LogoNotPng(content_type.to_string()));
+    }
+    Ok(())
+}
+
+/// GETs `url`, returning its content type and at most [`MAX_CONTENT_LEN`]
+/// bytes of body.
+async fn fetch(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(Option<String>, Vec<u8>), MetadataError> {
+    let fetch_err = |source| MetadataError::Fetch {
+        url: url.to_string(),
+        source,
+    };
+    let too_large = || MetadataError::TooLarge {
+        url: url.to_string(),
+    };
+
+    let mut response = client.get(url).send().await.map_err(fetch_err)?;
+    if !response.status().is_success() {
+        return Err(MetadataError::HttpStatus {
+            url: url.to_string(),
+            status: response.status(),
+        });
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_CONTENT_LEN as u64)
+    {
+        return Err(too_large());
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let mut body = vec![];
+    while let Some(chunk) = response.chunk().await.map_err(fetch_err)? {
+        if body.len() + chunk.len() > MAX_CONTENT_LEN {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((content_type, body))
+}
+
+fn validate_text(field: &'static str, value: &str) -> Result<(), MetadataError> {
+    if value.is_empty() {
+        return Err(MetadataError::Missing(field));
+    }
+    let valid = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == ' ' || TEXT_PUNCTUATION.contains(c));
+    if !valid {
+        return Err(MetadataError::InvalidText(field));
+    }
+    Ok(())
+}
+
+/// A [public URL](validate_public_url), reported against `field`.
+fn validate_url(field: &'static str, value: &str) -> Result<(), MetadataError> {
+    validate_public_url(value).map_err(|err| MetadataError::InvalidUrl {
+        field,
+        reason: err.to_string(),
+    })?;
+    Ok(())
+}
+
+fn validate_twitter(value: &str) -> Result<(), MetadataError> {
+    let invalid = || MetadataError::InvalidTwitter(value.to_string());
+    let url = Url::parse(value).map_err(|_| invalid())?;
+    let host = url.host_str().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    if !matches!(url.scheme(), "http" | "https") || !matches!(host, "twitter.com" | "x.com") {
+        return Err(invalid());
+    }
+    let handle = url.path().trim_start_matches('/').trim_end_matches('/');
+    let valid = !handle.is_empty()
+        && handle.len() <= MAX_TWITTER_HANDLE_LEN
+        && handle
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid || url.query().is_some() {
+        return Err(invalid());
+    }
+    Ok(())
+}
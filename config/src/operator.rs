@@ -0,0 +1,226 @@
+//! The `operator.yaml` format used by eigenlayer-cli.
+
+use crate::ConfigError;
+use alloy_primitives::Address;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use types::Operator;
+use url::Url;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AddressError {
+    #[error("{0} is missing the 0x prefix")]
+    MissingPrefix(String),
+    #[error("{address}: {source}")]
+    Checksum {
+        address: String,
+        source: alloy_primitives::AddressError,
+    },
+    #[error("{address}: {source}")]
+    Hex {
+        address: String,
+        source: alloy_primitives::hex::FromHexError,
+    },
+}
+
+errors::error_codes!(AddressError {
+    MissingPrefix => ("config.invalid_address", Validation),
+    Checksum => ("config.invalid_address_checksum", Validation),
+    Hex => ("config.invalid_address", Validation),
+});
+
+/// How long [`OperatorConfig::check_rpc`] waits for `eth_rpc_url`.
+pub const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerType {
+    LocalKeystore,
+    /// Accepted for eigenlayer-cli compatibility, but rejected by
+    /// [`OperatorConfig::validate`].
+    Fireblocks,
+    /// Accepted for eigenlayer-cli compatibility, but rejected by
+    /// [`OperatorConfig::validate`].
+    Web3,
+}
+
+impl std::fmt::Display for SignerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::LocalKeystore => "local_keystore",
+            Self::Fireblocks => "fireblocks",
+            Self::Web3 => "web3",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperatorDetails {
+    #[serde(deserialize_with = "checksummed")]
+    pub address: Address,
+    #[serde(deserialize_with = "checksummed", default)]
+    pub delegation_approver_address: Address,
+    #[serde(default)]
+    pub staker_opt_out_window_blocks: u32,
+    pub metadata_url: String,
+    #[serde(default)]
+    pub allocation_delay: u32,
+}
+
+impl From<&OperatorDetails> for Operator {
+    fn from(details: &OperatorDetails) -> Self {
+        Self {
+            address: details.address,
+            delegation_approver_address: details.delegation_approver_address,
+            metadata_url: details.metadata_url.clone(),
+            allocation_delay: details.allocation_delay,
+            staker_opt_out_window_blocks: details.staker_opt_out_window_blocks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperatorConfig {
+    pub operator: OperatorDetails,
+    #[serde(deserialize_with = "checksummed")]
+    pub el_delegation_manager_address: Address,
+    #[serde(deserialize_with = "checksummed_opt", default)]
+    pub el_avs_directory_address: Option<Address>,
+    #[serde(deserialize_with = "checksummed_opt", default)]
+    pub el_rewards_coordinator_address: Option<Address>,
+    pub eth_rpc_url: String,
+    pub chain_id: u64,
+    pub private_key_store_path: PathBuf,
+    pub signer_type: SignerType,
+}
+
+impl OperatorConfig {
+    /// Reads and [validates](Self::validate) an `operator.yaml`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let config = Self::from_yaml_str(&contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_yaml_str(yaml: &str) -> Result<Self, ConfigError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// The registration details, as sent to the DelegationManager.
+    pub fn operator(&self) -> Operator {
+        Operator::from(&self.operator)
+    }
+
+    /// Offline checks, including [`Operator::validate`]. Address syntax and
+    /// checksums are already enforced while parsing. Only `local_keystore`
+    /// signers are supported.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.operator().validate()?;
+        if self.el_delegation_manager_address.is_zero() {
+            return Err(ConfigError::ZeroAddress("el_delegation_manager_address"));
+        }
+        validate_url(
+            "eth_rpc_url",
+            &self.eth_rpc_url,
+            &["https", "http", "wss", "ws"],
+        )?;
+        if self.signer_type != SignerType::LocalKeystore {
+            return Err(ConfigError::UnsupportedSigner(self.signer_type));
+        }
+        if !self.private_key_store_path.is_file() {
+            return Err(ConfigError::KeystoreNotFound(
+                self.private_key_store_path.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Calls `eth_chainId` on `eth_rpc_url` and checks it matches `chain_id`.
+    /// Only HTTP RPC URLs can be checked this way. Gives up after
+    /// [`RPC_TIMEOUT`].
+    pub async fn check_rpc(&self) -> Result<(), ConfigError> {
+        let response: serde_json::Value = reqwest::Client::builder()
+            .timeout(RPC_TIMEOUT)
+            .build()?
+            .post(&self.eth_rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "eth_chainId",
+                "params": [],
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let result = response["result"].as_str().unwrap_or_default();
+        let actual = u64::from_str_radix(result.trim_start_matches("0x"), 16)
+            .map_err(|_| ConfigError::InvalidRpcResponse(response.to_string()))?;
+        if actual != self.chain_id {
+            return Err(ConfigError::ChainIdMismatch {
+                expected: self.chain_id,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn validate_url(field: &'static str, value: &str, schemes: &[&str]) -> Result<(), ConfigError> {
+    let url = Url::parse(value).map_err(|err| ConfigError::InvalidUrl {
+        field,
+        reason: err.to_string(),
+    })?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(ConfigError::InvalidUrl {
+            field,
+            reason: format!("scheme must be one of {}", schemes.join(", ")),
+        });
+    }
+    Ok(())
+}
+
+/// Parses a hex address, rejecting mixed-case addresses whose EIP-55 checksum
+/// doesn't match. All-lowercase and all-uppercase addresses carry no checksum.
+pub fn parse_address(s: &str) -> Result<Address, AddressError> {
+    let hex = s
+        .strip_prefix("0x")
+        .ok_or_else(|| AddressError::MissingPrefix(s.to_string()))?;
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        Address::parse_checksummed(s, None).map_err(|source| AddressError::Checksum {
+            address: s.to_string(),
+            source,
+        })
+    } else {
+        s.parse().map_err(|source| AddressError::Hex {
+            address: s.to_string(),
+            source,
+        })
+    }
+}
+
+fn checksummed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_address(&s).map_err(serde::de::Error::custom)
+}
+
+fn checksummed_opt<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Address>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(s) if !s.is_empty() => parse_address(&s)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,239 @@
+use config::{ConfigError, ConfigLoader};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    name: String,
+    port: u16,
+    verbose: bool,
+    quorums: Vec<u8>,
+    tags: Vec<String>,
+    alias: Option<String>,
+    max_peers: Option<u16>,
+    tls: Option<bool>,
+    metrics: Metrics,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Metrics {
+    addr: String,
+    interval_secs: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            name: "node".to_string(),
+            port: 9010,
+            verbose: false,
+            quorums: vec![0],
+            tags: vec![],
+            alias: None,
+            max_peers: None,
+            tls: None,
+            metrics: Metrics {
+                addr: "127.0.0.1:9090".to_string(),
+                interval_secs: 15,
+            },
+        }
+    }
+}
+
+fn file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("config-layered-{name}"));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn loader() -> ConfigLoader {
+    ConfigLoader::new()
+        .with_defaults(&Settings::default())
+        .unwrap()
+}
+
+#[test]
+fn layers_apply_in_precedence_order() {
+    let toml = file(
+        "precedence.toml",
+        "name = \"from-toml\"\nport = 1\n[metrics]\ninterval_secs = 30\n",
+    );
+    let yaml = file("precedence.yaml", "port: 2\nverbose: true\n");
+    std::env::set_var("LAYERED_PRECEDENCE_PORT", "3");
+    std::env::set_var("LAYERED_PRECEDENCE_METRICS__ADDR", "0.0.0.0:9090");
+
+    // Added in reverse; layers still apply defaults, files, env, overrides.
+    let settings: Settings = loader()
+        .with_override("port", "4")
+        .with_env("LAYERED_PRECEDENCE")
+        .with_file(&toml)
+        .with_file(&yaml)
+        .load()
+        .unwrap()
+        .extract()
+        .unwrap();
+
+    assert_eq!(settings.name, "from-toml");
+    assert!(settings.verbose);
+    assert_eq!(settings.port, 4);
+    assert_eq!(settings.metrics.addr, "0.0.0.0:9090");
+    assert_eq!(settings.metrics.interval_secs, 30);
+    assert_eq!(settings.quorums, [0]);
+}
+
+#[test]
+fn later_files_override_earlier_ones() {
+    let first = file("first.yml", "name: first\nport: 1\n");
+    let second = file("second.toml", "name = \"second\"\n");
+    let config = loader().with_file(first).with_file(second).load().unwrap();
+    assert_eq!(config.get::<String>("name").unwrap().unwrap(), "second");
+    assert_eq!(config.get::<u16>("port").unwrap(), Some(1));
+    assert_eq!(config.get::<u16>("missing.key").unwrap(), None);
+}
+
+#[test]
+fn env_keys_nest_on_double_underscores() {
+    std::env::set_var("LAYERED_NESTED_METRICS__INTERVAL_SECS", "60");
+    std::env::set_var("LAYERED_NESTED_EXTRA__DEEP__KEY", "value");
+    let config = loader().with_env("LAYERED_NESTED").load().unwrap();
+    assert_eq!(
+        config.get::<u64>("metrics.interval_secs").unwrap(),
+        Some(60)
+    );
+    assert_eq!(
+        config.get::<String>("extra.deep.key").unwrap().as_deref(),
+        Some("value")
+    );
+}
+
+#[test]
+fn overrides_are_typed_after_the_replaced_value() {
+    let settings: Settings = loader()
+        .with_override("name", "123")
+        .with_override("port", "9011")
+        .with_override("verbose", "true")
+        .with_override("quorums", "0, 1,2")
+        .with_override("tags", "a,b")
+        .with_override("alias", "node-a")
+        .with_override("max_peers", "8080")
+        .with_override("tls", "true")
+        .load()
+        .unwrap()
+        .extract()
+        .unwrap();
+
+    assert_eq!(settings.name, "123");
+    assert_eq!(settings.port, 9011);
+    assert!(settings.verbose);
+    assert_eq!(settings.quorums, [0, 1, 2]);
+    assert_eq!(settings.tags, ["a", "b"]);
+    assert_eq!(settings.alias.as_deref(), Some("node-a"));
+    assert_eq!(settings.max_peers, Some(8080));
+    assert_eq!(settings.tls, Some(true));
+}
+
+#[test]
+fn unset_options_take_json_scalars() {
+    let settings: Settings = loader()
+        .with_override("alias", "\"123\"")
+        .with_override("max_peers", "null")
+        .with_override("tls", "false")
+        .load()
+        .unwrap()
+        .extract()
+        .unwrap();
+    assert_eq!(settings.alias.as_deref(), Some("123"));
+    assert_eq!(settings.max_peers, None);
+    assert_eq!(settings.tls, Some(false));
+
+    let err = loader()
+        .with_override("max_peers", "many")
+        .load()
+        .unwrap()
+        .extract::<Settings>()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::Invalid(_)), "{err}");
+}
+
+#[test]
+fn empty_arrays_take_strings_or_json() {
+    #[derive(Serialize, Deserialize, Default)]
+    struct Lists {
+        numbers: Vec<u8>,
+        names: Vec<String>,
+    }
+    let lists: Lists = ConfigLoader::new()
+        .with_defaults(&Lists::default())
+        .unwrap()
+        .with_override("numbers", "[0,1]")
+        .with_override("names", "1,2")
+        .load()
+        .unwrap()
+        .extract()
+        .unwrap();
+    assert_eq!(lists.numbers, [0, 1]);
+    assert_eq!(lists.names, ["1", "2"]);
+
+    let empty: Settings = loader()
+        .with_override("quorums", "")
+        .load()
+        .unwrap()
+        .extract()
+        .unwrap();
+    assert!(empty.quorums.is_empty());
+}
+
+#[test]
+fn mistyped_values_fail_extraction() {
+    let err = loader()
+        .with_override("port", "not-a-port")
+        .load()
+        .unwrap()
+        .extract::<Settings>()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::Invalid(_)));
+}
+
+#[test]
+fn parses_override_args() {
+    let config = loader()
+        .with_override_arg("metrics.addr=0.0.0.0:1=2")
+        .unwrap()
+        .load()
+        .unwrap();
+    assert_eq!(
+        config.get::<String>("metrics.addr").unwrap().as_deref(),
+        Some("0.0.0.0:1=2")
+    );
+    for arg in ["no-equals", "=value"] {
+        assert!(matches!(
+            loader().with_override_arg(arg),
+            Err(ConfigError::InvalidOverride(_))
+        ));
+    }
+}
+
+#[test]
+fn rejects_unknown_file_formats() {
+    let path = file("settings.json", "{}");
+    assert!(matches!(
+        loader().with_file(path).load(),
+        Err(ConfigError::UnsupportedFormat(_))
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn tolerates_non_utf8_environment() {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    std::env::set_var("LAYERED_UNRELATED", OsString::from_vec(vec![0xff, 0xfe]));
+    loader().with_env("LAYERED_TOLERANT").load().unwrap();
+
+    std::env::set_var("LAYERED_OURS_NAME", OsString::from_vec(vec![0xff]));
+    assert!(matches!(
+        loader().with_env("LAYERED_OURS").load(),
+        Err(ConfigError::NonUnicodeEnv(name)) if name == "LAYERED_OURS_NAME"
+    ));
+}
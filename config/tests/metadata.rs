@@ -0,0 +1,165 @@
+use config::metadata::{check_logo, fetch_and_validate, MAX_DESCRIPTION_LEN};
+use config::{MetadataError, OperatorMetadata};
+
+fn metadata() -> OperatorMetadata {
+    OperatorMetadata {
+        name: "Example Operator".to_string(),
+        website: "https://example.com".to_string(),
+        description: "Operates AVSs since 2024 (mainnet & holesky).".to_string(),
+        logo: "https://raw.githubusercontent.com/example/meta/main/logo.png".to_string(),
+        twitter: "https://x.com/example_op".to_string(),
+    }
+}
+
+#[test]
+fn accepts_valid_metadata() {
+    metadata().validate().unwrap();
+    OperatorMetadata {
+        website: String::new(),
+        twitter: String::new(),
+        ..metadata()
+    }
+    .validate()
+    .unwrap();
+}
+
+#[test]
+fn parses_metadata_json() {
+    let json = r#"{
+        "name": "Example Operator",
+        "description": "An operator",
+        "logo": "https://example.com/logo.png"
+    }"#;
+    let parsed: OperatorMetadata = serde_json::from_str(json).unwrap();
+    assert_eq!(parsed.website, "");
+    parsed.validate().unwrap();
+}
+
+#[test]
+fn rejects_missing_and_invalid_text() {
+    let err = OperatorMetadata {
+        name: String::new(),
+        ..metadata()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(matches!(err, MetadataError::Missing("name")));
+
+    let err = OperatorMetadata {
+        name: "<script>".to_string(),
+        ..metadata()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(matches!(err, MetadataError::InvalidText("name")));
+
+    let err = OperatorMetadata {
+        description: "a".repeat(MAX_DESCRIPTION_LEN + 1),
+        ..metadata()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        MetadataError::TooLong {
+            field: "description",
+            ..
+        }
+    ));
+
+    let err = OperatorMetadata {
+        logo: String::new(),
+        ..metadata()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(matches!(err, MetadataError::Missing("logo")));
+}
+
+#[test]
+fn rejects_invalid_urls() {
+    for (website, reason) in [
+        ("example.com", "relative URL without a base"),
+        ("ftp://example.com", "scheme must be http or https"),
+        ("http://localhost:8080", "host must be publicly reachable"),
+        ("http://192.168.1.10/", "host must be publicly reachable"),
+    ] {
+        let err = OperatorMetadata {
+            website: website.to_string(),
+            ..metadata()
+        }
+        .validate()
+        .unwrap_err();
+        match err {
+            MetadataError::InvalidUrl { field, reason: r } => {
+                assert_eq!(field, "website");
+                assert_eq!(r, reason, "{website}");
+            }
+            err => panic!("{website}: {err}"),
+        }
+    }
+}
+
+#[test]
+fn checks_twitter_urls() {
+    for twitter in [
+        "https://twitter.com/example",
+        "https://www.x.com/example/",
+        "http://x.com/under_score",
+    ] {
+        OperatorMetadata {
+            twitter: twitter.to_string(),
+            ..metadata()
+        }
+        .validate()
+        .unwrap();
+    }
+    for twitter in [
+        "@example",
+        "https://facebook.com/example",
+        "https://x.com/",
+        "https://x.com/a_handle_that_is_too_long",
+        "https://x.com/example/status/1",
+    ] {
+        let err = OperatorMetadata {
+            twitter: twitter.to_string(),
+            ..metadata()
+        }
+        .validate()
+        .unwrap_err();
+        assert!(matches!(err, MetadataError::InvalidTwitter(_)), "{twitter}");
+    }
+}
+
+#[test]
+fn checks_logo_is_png() {
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    check_logo(Some("image/png"), png).unwrap();
+    check_logo(Some("image/png; charset=binary"), png).unwrap();
+    assert!(matches!(
+        check_logo(Some("image/jpeg"), png),
+        Err(MetadataError::LogoNotPng(_))
+    ));
+    assert!(matches!(
+        check_logo(Some("image/png"), b"GIF89a"),
+        Err(MetadataError::LogoNotPng(_))
+    ));
+    assert!(matches!(
+        check_logo(None, png),
+        Err(MetadataError::LogoNotPng(_))
+    ));
+}
+
+#[tokio::test]
+async fn fetch_rejects_local_metadata_urls() {
+    let err = fetch_and_validate("http://127.0.0.1:1/metadata.json")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        MetadataError::InvalidUrl {
+            field: "metadata_url",
+            ..
+        }
+    ));
+}
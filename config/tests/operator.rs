@@ -0,0 +1,220 @@
+use axum::routing::post;
+use axum::{Json, Router};
+use config::operator::SignerType;
+use config::{AddressError, ConfigError, OperatorConfig};
+use errors::{EigenError, ErrorCategory};
+use std::path::PathBuf;
+use types::operator::UrlError;
+use types::OperatorError;
+
+// From the EIP-55 test vectors.
+const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+const BAD_CHECKSUM: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+
+fn keystore() -> PathBuf {
+    let path = std::env::temp_dir().join("config-operator-test.ecdsa.key.json");
+    std::fs::write(&path, "{}").unwrap();
+    path
+}
+
+fn yaml(address: &str, signer_type: &str) -> String {
+    format!(
+        r#"
+operator:
+  address: {address}
+  delegation_approver_address: "0x0000000000000000000000000000000000000000"
+  staker_opt_out_window_blocks: 0
+  metadata_url: https://example.com/metadata.json
+el_delegation_manager_address: "0x39053D51B77DC0d36036Fc1fCc8Cb819df8Ef37A"
+eth_rpc_url: https://ethereum-holesky-rpc.publicnode.com
+chain_id: 17000
+private_key_store_path: {keystore}
+signer_type: {signer_type}
+"#,
+        keystore = keystore().display(),
+    )
+}
+
+#[test]
+fn accepts_checksummed_and_single_case_addresses() {
+    for address in [
+        CHECKSUMMED.to_string(),
+        CHECKSUMMED.to_lowercase(),
+        format!("0x{}", CHECKSUMMED[2..].to_uppercase()),
+    ] {
+        let config = OperatorConfig::from_yaml_str(&yaml(&address, "local_keystore")).unwrap();
+        assert_eq!(
+            config.operator.address,
+            config::operator::parse_address(CHECKSUMMED).unwrap()
+        );
+        config.validate().unwrap();
+    }
+}
+
+#[test]
+fn rejects_bad_checksums() {
+    let err = OperatorConfig::from_yaml_str(&yaml(BAD_CHECKSUM, "local_keystore")).unwrap_err();
+    assert!(matches!(err, ConfigError::Yaml(_)));
+    assert!(err.to_string().contains(BAD_CHECKSUM), "{err}");
+
+    assert!(matches!(
+        config::operator::parse_address(BAD_CHECKSUM),
+        Err(AddressError::Checksum { .. })
+    ));
+    assert!(matches!(
+        config::operator::parse_address(&CHECKSUMMED[2..]),
+        Err(AddressError::MissingPrefix(_))
+    ));
+}
+
+#[test]
+fn rejects_zero_operator_address() {
+    let config = OperatorConfig::from_yaml_str(&yaml(
+        "\"0x0000000000000000000000000000000000000000\"",
+        "local_keystore",
+    ))
+    .unwrap();
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::Operator(OperatorError::ZeroAddress))
+    ));
+}
+
+#[test]
+fn rejects_unknown_signer_type() {
+    let err = OperatorConfig::from_yaml_str(&yaml(CHECKSUMMED, "hsm")).unwrap_err();
+    assert!(err.to_string().contains("unknown variant `hsm`"), "{err}");
+}
+
+#[test]
+fn rejects_unsupported_signer_types() {
+    for (name, signer_type) in [
+        ("fireblocks", SignerType::Fireblocks),
+        ("web3", SignerType::Web3),
+    ] {
+        let config = OperatorConfig::from_yaml_str(&yaml(CHECKSUMMED, name)).unwrap();
+        assert_eq!(config.signer_type, signer_type);
+        let err = config.validate().unwrap_err();
+        assert!(matches!(err, ConfigError::UnsupportedSigner(s) if s == signer_type));
+        assert_eq!(
+            err.to_string(),
+            format!("signer_type {name} is not supported")
+        );
+    }
+}
+
+#[test]
+fn rejects_missing_keystore() {
+    let mut config = OperatorConfig::from_yaml_str(&yaml(CHECKSUMMED, "local_keystore")).unwrap();
+    config.private_key_store_path = PathBuf::from("/nonexistent/key.json");
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::KeystoreNotFound(_))
+    ));
+}
+
+#[test]
+fn applies_operator_validation() {
+    let mut config = OperatorConfig::from_yaml_str(&yaml(CHECKSUMMED, "local_keystore")).unwrap();
+    assert_eq!(config.operator().allocation_delay, 0);
+
+    for url in [
+        "http://localhost/metadata.json",
+        "http://192.168.1.10/metadata.json",
+    ] {
+        config.operator.metadata_url = url.to_string();
+        assert!(
+            matches!(
+                config.validate(),
+                Err(ConfigError::Operator(OperatorError::InvalidMetadataUrl(
+                    UrlError::NotPublic
+                )))
+            ),
+            "{url}"
+        );
+    }
+
+    config.operator.metadata_url = format!("https://example.com/{}", "a".repeat(1024));
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::Operator(OperatorError::InvalidMetadataUrl(
+            UrlError::TooLong
+        )))
+    ));
+}
+
+#[test]
+fn reads_allocation_delay() {
+    let yaml = yaml(CHECKSUMMED, "local_keystore").replace(
+        "  metadata_url:",
+        "  allocation_delay: 126000\n  metadata_url:",
+    );
+    let config = OperatorConfig::from_yaml_str(&yaml).unwrap();
+    assert_eq!(config.operator().allocation_delay, 126000);
+}
+
+/// An RPC stand-in that answers every request with `body`.
+async fn rpc(body: &'static str) -> String {
+    let router = Router::new().route("/", post(move || async move { body }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    url
+}
+
+async fn check_rpc(url: String) -> Result<(), ConfigError> {
+    let mut config = OperatorConfig::from_yaml_str(&yaml(CHECKSUMMED, "local_keystore")).unwrap();
+    config.eth_rpc_url = url;
+    config.check_rpc().await
+}
+
+#[tokio::test]
+async fn check_rpc_compares_chain_ids() {
+    // 0x4268 is Holesky's 17000.
+    check_rpc(rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x4268"}"#).await)
+        .await
+        .unwrap();
+
+    let err = check_rpc(rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#).await)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ConfigError::ChainIdMismatch {
+            expected: 17000,
+            actual: 1
+        }
+    ));
+
+    let err = EigenError::from(err);
+    assert_eq!(err.code(), "config.chain_id_mismatch");
+    assert_eq!(err.category(), ErrorCategory::Validation);
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(ConfigError::ChainIdMismatch { .. })
+    ));
+}
+
+#[tokio::test]
+async fn check_rpc_rejects_bad_responses() {
+    let err = check_rpc(rpc(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601}}"#).await)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidRpcResponse(_)), "{err}");
+
+    let err = check_rpc(rpc("not json").await).await.unwrap_err();
+    assert!(matches!(err, ConfigError::Rpc(_)), "{err}");
+
+    let router = Router::new().route(
+        "/",
+        post(|Json(request): Json<serde_json::Value>| async move {
+            assert_eq!(request["method"], "eth_chainId");
+            axum::http::StatusCode::BAD_GATEWAY
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    let err = check_rpc(url).await.unwrap_err();
+    assert!(matches!(err, ConfigError::Rpc(_)), "{err}");
+}
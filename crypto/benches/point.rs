@@ -0,0 +1,39 @@
+//! Point (de)serialization between ark and the Solidity layout. BLS signing,
+//! verification and aggregation benches belong here once those are ported.
+
+use alloy_sol_types::SolValue;
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crypto::point::{abi, G1Point, G2Point};
+
+fn points(c: &mut Criterion) {
+    let scalar = Fr::from(0x1234_5678_9abc_def0u64);
+    let g1 = G1Point((G1Affine::generator() * scalar).into_affine());
+    let g2 = G2Point((G2Affine::generator() * scalar).into_affine());
+    let g1_encoded = abi::G1Point::from(g1).abi_encode();
+    let g2_encoded = abi::G2Point::from(g2).abi_encode();
+
+    c.bench_function("g1_encode", |b| {
+        b.iter(|| abi::G1Point::from(black_box(g1)).abi_encode())
+    });
+    c.bench_function("g1_decode", |b| {
+        b.iter(|| {
+            let point = abi::G1Point::abi_decode(black_box(&g1_encoded)).unwrap();
+            G1Point::try_from(point).unwrap()
+        })
+    });
+    c.bench_function("g2_encode", |b| {
+        b.iter(|| abi::G2Point::from(black_box(g2)).abi_encode())
+    });
+    // Dominated by the subgroup check.
+    c.bench_function("g2_decode", |b| {
+        b.iter(|| {
+            let point = abi::G2Point::abi_decode(black_box(&g2_encoded)).unwrap();
+            G2Point::try_from(point).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, points);
+criterion_main!(benches);
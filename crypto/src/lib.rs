@@ -0,0 +1,12 @@
+//! BN254 primitives. Builds for `wasm32-unknown-unknown`, so browser
+//! dashboards and light clients can decode and check operator points; keep
+//! new dependencies free of OS and runtime requirements.
+//!
+//! Without the default `std` feature the crate is `no_std`, losing only the
+//! `errors` integration.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod point;
+#[cfg(feature = "proptest")]
+pub mod strategies;
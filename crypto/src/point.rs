@@ -0,0 +1,143 @@
+//! BN254 points and the `BN254.G1Point`/`BN254.G2Point` structs the
+//! RegistryCoordinator and BLSSignatureChecker take.
+
+use alloy_primitives::U256;
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, PrimeField};
+
+pub mod abi {
+    alloy_sol_types::sol! {
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct G1Point {
+            uint256 X;
+            uint256 Y;
+        }
+
+        /// Coordinates are `[c1, c0]`, the order the EIP-197 precompiles expect.
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct G2Point {
+            uint256[2] X;
+            uint256[2] Y;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PointError {
+    #[error("coordinate is not less than the field modulus")]
+    InvalidCoordinate,
+    #[error("point is not on the curve")]
+    NotOnCurve,
+    #[error("point is not in the prime-order subgroup")]
+    NotInSubgroup,
+}
+
+#[cfg(feature = "std")]
+errors::error_codes!(PointError {
+    InvalidCoordinate => ("crypto.invalid_coordinate", Validation),
+    NotOnCurve => ("crypto.point_not_on_curve", Validation),
+    NotInSubgroup => ("crypto.point_not_in_subgroup", Validation),
+});
+
+/// A G1 point. The point at infinity is `(0, 0)` on chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct G1Point(pub G1Affine);
+
+/// A G2 point. The point at infinity is all zeros on chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct G2Point(pub G2Affine);
+
+impl G1Point {
+    pub fn generator() -> Self {
+        Self(G1Affine::generator())
+    }
+}
+
+impl G2Point {
+    pub fn generator() -> Self {
+        Self(G2Affine::generator())
+    }
+}
+
+impl From<G1Affine> for G1Point {
+    fn from(point: G1Affine) -> Self {
+        Self(point)
+    }
+}
+
+impl From<G2Affine> for G2Point {
+    fn from(point: G2Affine) -> Self {
+        Self(point)
+    }
+}
+
+impl From<G1Point> for abi::G1Point {
+    fn from(point: G1Point) -> Self {
+        let (x, y) = point.0.xy().unwrap_or_default();
+        Self {
+            X: fq_to_u256(x),
+            Y: fq_to_u256(y),
+        }
+    }
+}
+
+impl TryFrom<abi::G1Point> for G1Point {
+    type Error = PointError;
+
+    fn try_from(point: abi::G1Point) -> Result<Self, Self::Error> {
+        if point.X.is_zero() && point.Y.is_zero() {
+            return Ok(Self(G1Affine::identity()));
+        }
+        let point = G1Affine::new_unchecked(u256_to_fq(point.X)?, u256_to_fq(point.Y)?);
+        if !point.is_on_curve() {
+            return Err(PointError::NotOnCurve);
+        }
+        // G1 has cofactor 1, so every point on the curve is in the subgroup.
+        Ok(Self(point))
+    }
+}
+
+impl From<G2Point> for abi::G2Point {
+    fn from(point: G2Point) -> Self {
+        let (x, y) = point.0.xy().unwrap_or_default();
+        Self {
+            X: fq2_to_u256s(x),
+            Y: fq2_to_u256s(y),
+        }
+    }
+}
+
+impl TryFrom<abi::G2Point> for G2Point {
+    type Error = PointError;
+
+    fn try_from(point: abi::G2Point) -> Result<Self, Self::Error> {
+        if point.X.iter().chain(&point.Y).all(U256::is_zero) {
+            return Ok(Self(G2Affine::identity()));
+        }
+        let point = G2Affine::new_unchecked(u256s_to_fq2(point.X)?, u256s_to_fq2(point.Y)?);
+        if !point.is_on_curve() {
+            return Err(PointError::NotOnCurve);
+        }
+        if !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(PointError::NotInSubgroup);
+        }
+        Ok(Self(point))
+    }
+}
+
+fn fq_to_u256(fq: Fq) -> U256 {
+    U256::from_limbs(fq.into_bigint().0)
+}
+
+fn u256_to_fq(value: U256) -> Result<Fq, PointError> {
+    Fq::from_bigint(BigInt::new(value.into_limbs())).ok_or(PointError::InvalidCoordinate)
+}
+
+fn fq2_to_u256s(fq2: Fq2) -> [U256; 2] {
+    [fq_to_u256(fq2.c1), fq_to_u256(fq2.c0)]
+}
+
+fn u256s_to_fq2([c1, c0]: [U256; 2]) -> Result<Fq2, PointError> {
+    Ok(Fq2::new(u256_to_fq(c0)?, u256_to_fq(c1)?))
+}
@@ -0,0 +1,25 @@
+//! proptest strategies for BN254 points, including the point at infinity.
+
+use crate::point::{G1Point, G2Point};
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use proptest::prelude::*;
+
+pub fn scalar() -> impl Strategy<Value = Fr> {
+    any::<[u8; 32]>().prop_map(|bytes| Fr::from_le_bytes_mod_order(&bytes))
+}
+
+pub fn g1_point() -> impl Strategy<Value = G1Point> {
+    prop_oneof![
+        1 => Just(G1Point(G1Affine::identity())),
+        15 => scalar().prop_map(|s| G1Point((G1Affine::generator() * s).into_affine())),
+    ]
+}
+
+pub fn g2_point() -> impl Strategy<Value = G2Point> {
+    prop_oneof![
+        1 => Just(G2Point(G2Affine::identity())),
+        15 => scalar().prop_map(|s| G2Point((G2Affine::generator() * s).into_affine())),
+    ]
+}
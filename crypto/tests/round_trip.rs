@@ -0,0 +1,60 @@
+use alloy_primitives::U256;
+use alloy_sol_types::SolValue;
+use crypto::point::{abi, G1Point, G2Point, PointError};
+use crypto::strategies::{g1_point, g2_point};
+use errors::{EigenError, ErrorCategory};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn g1_abi_round_trips(point in g1_point()) {
+        let encoded = abi::G1Point::from(point).abi_encode();
+        prop_assert_eq!(encoded.len(), 64);
+        let decoded = abi::G1Point::abi_decode(&encoded).unwrap();
+        prop_assert_eq!(G1Point::try_from(decoded).unwrap(), point);
+    }
+
+    #[test]
+    fn g2_abi_round_trips(point in g2_point()) {
+        let encoded = abi::G2Point::from(point).abi_encode();
+        prop_assert_eq!(encoded.len(), 128);
+        let decoded = abi::G2Point::abi_decode(&encoded).unwrap();
+        prop_assert_eq!(G2Point::try_from(decoded).unwrap(), point);
+    }
+
+    #[test]
+    fn g2_coordinates_are_swapped(point in g2_point()) {
+        // Swapping back to [c0, c1] must not give the same point, or a
+        // mis-ordered encoding would go unnoticed.
+        let abi = abi::G2Point::from(point);
+        prop_assume!(abi.X[0] != abi.X[1] || abi.Y[0] != abi.Y[1]);
+        let swapped = abi::G2Point {
+            X: [abi.X[1], abi.X[0]],
+            Y: [abi.Y[1], abi.Y[0]],
+        };
+        prop_assert!(G2Point::try_from(swapped).is_err());
+    }
+
+    #[test]
+    fn g1_off_curve_is_rejected(point in g1_point(), delta in 1u64..) {
+        let abi::G1Point { X, Y } = point.into();
+        prop_assume!(!(X.is_zero() && Y.is_zero()));
+        let moved = abi::G1Point { X, Y: Y.wrapping_add(U256::from(delta)) };
+        prop_assert!(matches!(
+            G1Point::try_from(moved),
+            Err(PointError::NotOnCurve | PointError::InvalidCoordinate)
+        ));
+    }
+}
+
+#[test]
+fn point_errors_carry_codes() {
+    let off_curve = abi::G1Point {
+        X: U256::from(1),
+        Y: U256::from(3),
+    };
+    let err = EigenError::from(G1Point::try_from(off_curve).unwrap_err());
+    assert_eq!(err.code(), "crypto.point_not_on_curve");
+    assert_eq!(err.category(), ErrorCategory::Validation);
+    assert_eq!(err.downcast_ref(), Some(&PointError::NotOnCurve));
+}
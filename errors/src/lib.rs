@@ -0,0 +1,129 @@
+//! Error codes shared by every crate's error enum, and [`EigenError`], which
+//! any of them converts into so applications can branch on the failure kind
+//! without matching each crate's enum.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The Ethereum RPC node failed or returned something unexpected.
+    Rpc,
+    Signer,
+    ContractRevert,
+    /// An input or configuration value was rejected. Retrying won't help.
+    Validation,
+    /// A non-RPC network call failed, e.g. to a metrics collector.
+    Network,
+    Io,
+    Internal,
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Rpc => "rpc",
+            Self::Signer => "signer",
+            Self::ContractRevert => "contract_revert",
+            Self::Validation => "validation",
+            Self::Network => "network",
+            Self::Io => "io",
+            Self::Internal => "internal",
+        };
+        f.write_str(name)
+    }
+}
+
+pub trait ErrorCode: Error + Send + Sync + 'static {
+    /// A stable identifier such as `"config.chain_id_mismatch"`. Codes are
+    /// never reused or renamed once released.
+    fn code(&self) -> &'static str;
+
+    fn category(&self) -> ErrorCategory;
+}
+
+impl ErrorCode for std::io::Error {
+    fn code(&self) -> &'static str {
+        "io"
+    }
+
+    fn category(&self) -> ErrorCategory {
+        ErrorCategory::Io
+    }
+}
+
+pub struct EigenError {
+    inner: Box<dyn ErrorCode>,
+}
+
+impl EigenError {
+    pub fn code(&self) -> &'static str {
+        self.inner.code()
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        self.inner.category()
+    }
+
+    /// The crate-level error this was converted from.
+    pub fn downcast_ref<E: ErrorCode>(&self) -> Option<&E> {
+        let inner: &dyn Error = &*self.inner;
+        inner.downcast_ref()
+    }
+}
+
+impl<E: ErrorCode> From<E> for EigenError {
+    fn from(err: E) -> Self {
+        Self {
+            inner: Box::new(err),
+        }
+    }
+}
+
+impl fmt::Display for EigenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.inner)
+    }
+}
+
+// `main` returning `Err` prints `Debug`, so keep it readable.
+impl fmt::Debug for EigenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)?;
+        let mut source = self.inner.source();
+        while let Some(err) = source {
+            write!(f, "\n  caused by: {err}")?;
+            source = err.source();
+        }
+        Ok(())
+    }
+}
+
+impl Error for EigenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.inner)
+    }
+}
+
+/// Implements [`ErrorCode`] for an error enum from a table of
+/// `Variant => (code, category)` entries.
+#[macro_export]
+macro_rules! error_codes {
+    ($ty:ty { $($variant:ident => ($code:literal, $category:ident)),+ $(,)? }) => {
+        impl $crate::ErrorCode for $ty {
+            fn code(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => $code,)+
+                }
+            }
+
+            fn category(&self) -> $crate::ErrorCategory {
+                match self {
+                    $(Self::$variant { .. } => $crate::ErrorCategory::$category,)+
+                }
+            }
+        }
+    };
+}
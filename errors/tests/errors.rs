@@ -0,0 +1,70 @@
+use errors::{EigenError, ErrorCategory, ErrorCode};
+
+#[derive(Debug, thiserror::Error)]
+enum FetchError {
+    #[error("timed out")]
+    Timeout,
+    #[error("bad status {0}")]
+    Status(u16),
+    #[error("failed to read {path}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+errors::error_codes!(FetchError {
+    Timeout => ("test.timeout", Network),
+    Status => ("test.bad_status", Rpc),
+    Read => ("test.read", Io),
+});
+
+fn read_error() -> FetchError {
+    FetchError::Read {
+        path: "key.json".to_string(),
+        source: std::io::Error::new(std::io::ErrorKind::NotFound, "no such file"),
+    }
+}
+
+#[test]
+fn macro_maps_every_variant_shape() {
+    assert_eq!(FetchError::Timeout.code(), "test.timeout");
+    assert_eq!(FetchError::Timeout.category(), ErrorCategory::Network);
+    assert_eq!(FetchError::Status(502).code(), "test.bad_status");
+    assert_eq!(FetchError::Status(502).category(), ErrorCategory::Rpc);
+    assert_eq!(read_error().code(), "test.read");
+    assert_eq!(read_error().category(), ErrorCategory::Io);
+}
+
+#[test]
+fn eigen_error_keeps_the_code_and_downcasts() {
+    let err = EigenError::from(FetchError::Status(502));
+    assert_eq!(err.code(), "test.bad_status");
+    assert_eq!(err.category(), ErrorCategory::Rpc);
+    assert!(matches!(
+        err.downcast_ref::<FetchError>(),
+        Some(FetchError::Status(502))
+    ));
+    assert!(err.downcast_ref::<std::io::Error>().is_none());
+
+    let io = EigenError::from(std::io::Error::other("disk full"));
+    assert_eq!((io.code(), io.category()), ("io", ErrorCategory::Io));
+    assert!(io.downcast_ref::<std::io::Error>().is_some());
+    assert!(io.downcast_ref::<FetchError>().is_none());
+}
+
+#[test]
+fn eigen_error_formats_the_code_and_causes() {
+    let err = EigenError::from(read_error());
+    assert_eq!(err.to_string(), "[test.read] failed to read key.json");
+    assert_eq!(
+        format!("{err:?}"),
+        "[test.read] failed to read key.json\n  caused by: no such file"
+    );
+}
+
+#[test]
+fn categories_display_in_snake_case() {
+    assert_eq!(ErrorCategory::ContractRevert.to_string(), "contract_revert");
+    assert_eq!(ErrorCategory::Validation.to_string(), "validation");
+}
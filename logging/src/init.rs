@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::env::VarError;
+use std::fmt;
+use tracing::Span;
+use tracing_subscriber::filter::{EnvFilter, ParseError};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoggingError {
+    #[error("invalid log filter: {0}")]
+    Filter(#[from] ParseError),
+    #[error("RUST_LOG is not valid UTF-8")]
+    NonUnicodeEnv,
+    #[error(transparent)]
+    Init(#[from] TryInitError),
+}
+
+errors::error_codes!(LoggingError {
+    Filter => ("logging.invalid_filter", Validation),
+    NonUnicodeEnv => ("logging.invalid_filter", Validation),
+    Init => ("logging.already_initialized", Internal),
+});
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// One JSON object per line, including the fields of enclosing spans.
+    #[default]
+    Json,
+    /// Multi-line, human-readable output for local development.
+    Pretty,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// An `EnvFilter` directive such as `"info,services=debug"`. `RUST_LOG`
+    /// takes precedence when set.
+    pub filter: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Json,
+            filter: "info".to_string(),
+        }
+    }
+}
+
+impl LogConfig {
+    pub fn pretty() -> Self {
+        Self {
+            format: LogFormat::Pretty,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+}
+
+/// Installs the global `tracing` subscriber. Fails if one is already set, or
+/// if `RUST_LOG` is set but invalid.
+pub fn init_logging(config: LogConfig) -> Result<(), LoggingError> {
+    let filter = match std::env::var(EnvFilter::DEFAULT_ENV) {
+        Ok(directives) => EnvFilter::builder().parse(directives)?,
+        Err(VarError::NotPresent) => EnvFilter::builder().parse(&config.filter)?,
+        Err(VarError::NotUnicode(_)) => return Err(LoggingError::NonUnicodeEnv),
+    };
+    let registry = tracing_subscriber::registry().with(filter);
+    match config.format {
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true),
+            )
+            .try_init()?,
+        LogFormat::Pretty => registry
+            .with(tracing_subscriber::fmt::layer().pretty())
+            .try_init()?,
+    }
+    Ok(())
+}
+
+/// A span tagging everything logged inside it with `task_index`.
+pub fn task_span(task_index: impl fmt::Display) -> Span {
+    tracing::info_span!("task", task_index = %task_index)
+}
+
+/// A span tagging everything logged inside it with `operator_id`.
+pub fn operator_span(operator_id: impl fmt::Display) -> Span {
+    tracing::info_span!("operator", operator_id = %operator_id)
+}
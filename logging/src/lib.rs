@@ -0,0 +1,103 @@
+//! The logging interface the SDK crates log through. Embedders can hand them
+//! their own [`Logger`] to route SDK logs into an existing logging stack.
+
+mod init;
+
+pub use init::{init_logging, operator_span, task_span, LogConfig, LogFormat, LoggingError};
+
+use std::fmt;
+use std::sync::Arc;
+
+pub type SharedLogger = Arc<dyn Logger>;
+
+/// Key-value pairs attached to a log line.
+pub type Fields<'a> = &'a [(&'a str, &'a dyn fmt::Display)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+pub trait Logger: Send + Sync {
+    fn log(&self, level: Level, msg: &str, fields: Fields<'_>);
+
+    fn debug(&self, msg: &str, fields: Fields<'_>) {
+        self.log(Level::Debug, msg, fields);
+    }
+
+    fn info(&self, msg: &str, fields: Fields<'_>) {
+        self.log(Level::Info, msg, fields);
+    }
+
+    fn warn(&self, msg: &str, fields: Fields<'_>) {
+        self.log(Level::Warn, msg, fields);
+    }
+
+    fn error(&self, msg: &str, fields: Fields<'_>) {
+        self.log(Level::Error, msg, fields);
+    }
+}
+
+/// Emits `tracing` events. tracing needs field names at compile time, so the
+/// [`KNOWN_FIELDS`] are recorded as real fields (and can be queried as such in
+/// JSON logs), and any other fields as one `fields` value holding a JSON
+/// object, e.g. `{"skipped":"3"}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingLogger;
+
+/// Keys [`TracingLogger`] records as tracing fields of their own.
+pub const KNOWN_FIELDS: [&str; 4] = ["service", "operator_id", "task_index", "error"];
+
+impl Logger for TracingLogger {
+    fn log(&self, level: Level, msg: &str, fields: Fields<'_>) {
+        let known = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| tracing::field::display(value))
+        };
+        let (service, operator_id, task_index, error) = (
+            known("service"),
+            known("operator_id"),
+            known("task_index"),
+            known("error"),
+        );
+        let extra: serde_json::Map<_, _> = fields
+            .iter()
+            .filter(|(key, _)| !KNOWN_FIELDS.contains(key))
+            .map(|(key, value)| (key.to_string(), value.to_string().into()))
+            .collect();
+        let extra = (!extra.is_empty()).then(|| serde_json::Value::from(extra).to_string());
+
+        macro_rules! emit {
+            ($level:expr) => {
+                tracing::event!(
+                    $level,
+                    service,
+                    operator_id,
+                    task_index,
+                    error,
+                    fields = extra.as_deref(),
+                    "{msg}"
+                )
+            };
+        }
+        match level {
+            Level::Debug => emit!(tracing::Level::DEBUG),
+            Level::Info => emit!(tracing::Level::INFO),
+            Level::Warn => emit!(tracing::Level::WARN),
+            Level::Error => emit!(tracing::Level::ERROR),
+        }
+    }
+}
+
+/// Drops everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopLogger;
+
+impl Logger for NoopLogger {
+    fn log(&self, _: Level, _: &str, _: Fields<'_>) {}
+}
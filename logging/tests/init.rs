@@ -0,0 +1,26 @@
+use errors::{EigenError, ErrorCategory};
+use logging::{init_logging, LogConfig, LoggingError};
+
+// One test, since RUST_LOG and the global subscriber are process-wide.
+#[test]
+fn rust_log_takes_precedence_and_is_validated() {
+    std::env::set_var("RUST_LOG", "services=loud");
+    let err = EigenError::from(init_logging(LogConfig::default()).unwrap_err());
+    assert_eq!(err.code(), "logging.invalid_filter");
+    assert_eq!(err.category(), ErrorCategory::Validation);
+    assert!(matches!(err.downcast_ref(), Some(LoggingError::Filter(_))));
+
+    std::env::remove_var("RUST_LOG");
+    assert!(matches!(
+        init_logging(LogConfig::default().with_filter("services=loud")),
+        Err(LoggingError::Filter(_))
+    ));
+
+    std::env::set_var("RUST_LOG", "debug");
+    init_logging(LogConfig::default().with_filter("services=loud")).unwrap();
+    assert!(tracing::enabled!(tracing::Level::DEBUG));
+    assert!(matches!(
+        init_logging(LogConfig::default()),
+        Err(LoggingError::Init(_))
+    ));
+}
@@ -0,0 +1,72 @@
+use logging::{Logger, TracingLogger};
+use std::io;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Buffer {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self {
+        self.clone()
+    }
+}
+
+/// Logs through [`TracingLogger`] into a JSON subscriber and returns the
+/// event's fields.
+fn json_fields(log: impl FnOnce(&TracingLogger)) -> serde_json::Value {
+    let buffer = Buffer::default();
+    let subscriber = tracing_subscriber::fmt()
+        .json()
+        .with_writer(buffer.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, || log(&TracingLogger));
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    line["fields"].clone()
+}
+
+#[test]
+fn records_known_keys_as_fields() {
+    let fields = json_fields(|logger| {
+        logger.error(
+            "service failed",
+            &[("service", &"aggregator"), ("error", &"connection refused")],
+        )
+    });
+    assert_eq!(fields["message"], "service failed");
+    assert_eq!(fields["service"], "aggregator");
+    assert_eq!(fields["error"], "connection refused");
+    assert!(fields.get("fields").is_none());
+    assert!(fields.get("operator_id").is_none());
+}
+
+#[test]
+fn collects_other_keys_into_a_json_object() {
+    let fields = json_fields(|logger| {
+        logger.warn(
+            "subscriber lagged",
+            &[("task_index", &7), ("skipped", &3), ("url", &"http://gw")],
+        )
+    });
+    assert_eq!(fields["task_index"], "7");
+    let extra: serde_json::Value =
+        serde_json::from_str(fields["fields"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        extra,
+        serde_json::json!({ "skipped": "3", "url": "http://gw" })
+    );
+}
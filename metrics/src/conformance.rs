@@ -0,0 +1,183 @@
+//! Checks a registry against the EigenLayer metrics spec naming and label
+//! rules, so AVS teams can catch dashboard-breaking renames in their tests:
+//!
+//! ```
+//! let registry = prometheus::Registry::new();
+//! metrics::conformance::ConformanceChecker::new()
+//!     .allow_prefix("myavs_")
+//!     .assert_conformant(&registry);
+//! ```
+
+use crate::eigenmetrics::NAMESPACE;
+use prometheus::proto::{MetricFamily, MetricType};
+use prometheus::Registry;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Metrics whose name, type and label set are fixed by the spec, and whether
+/// every AVS node must expose them.
+const SPEC_METRICS: &[(&str, MetricType, &[&str], bool)] = &[
+    (
+        "eigen_fees_earned_total",
+        MetricType::COUNTER,
+        &["avs_name", "token"],
+        true,
+    ),
+    (
+        "eigen_performance_score",
+        MetricType::GAUGE,
+        &["avs_name"],
+        true,
+    ),
+    (
+        "eigen_registered_stakes",
+        MetricType::GAUGE,
+        &["avs_name", "quorum_name", "quorum_number"],
+        false,
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub metric: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.metric, self.reason)
+    }
+}
+
+pub struct ConformanceChecker {
+    prefixes: Vec<String>,
+    require_spec_metrics: bool,
+}
+
+impl Default for ConformanceChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConformanceChecker {
+    /// Accepts `eigen_` metrics and the standard `process_` collector metrics.
+    pub fn new() -> Self {
+        Self {
+            prefixes: vec![format!("{NAMESPACE}_"), "process_".to_string()],
+            require_spec_metrics: false,
+        }
+    }
+
+    /// Also accepts the AVS's own metrics namespace, e.g. `"myavs_"`.
+    pub fn allow_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefixes.push(prefix.into());
+        self
+    }
+
+    /// Reports mandatory spec metrics missing from the registry. Labelled
+    /// metrics such as `eigen_fees_earned_total` only show up once a value has
+    /// been recorded.
+    pub fn require_spec_metrics(mut self) -> Self {
+        self.require_spec_metrics = true;
+        self
+    }
+
+    pub fn check(&self, registry: &Registry) -> Vec<Violation> {
+        let families = registry.gather();
+        let mut violations = vec![];
+        for family in &families {
+            self.check_family(family, &mut violations);
+        }
+
+        if self.require_spec_metrics {
+            for (name, _, _, required) in SPEC_METRICS {
+                if *required && !families.iter().any(|f| f.name() == *name) {
+                    violations.push(Violation {
+                        metric: name.to_string(),
+                        reason: "required by the spec but not registered".to_string(),
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Panics listing every violation, for use in tests.
+    pub fn assert_conformant(&self, registry: &Registry) {
+        let violations = self.check(registry);
+        if !violations.is_empty() {
+            let list: Vec<_> = violations.iter().map(|v| format!("  {v}")).collect();
+            panic!(
+                "{} metrics spec violation(s):\n{}",
+                violations.len(),
+                list.join("\n")
+            );
+        }
+    }
+
+    fn check_family(&self, family: &MetricFamily, violations: &mut Vec<Violation>) {
+        let name = family.name();
+        let mut violation = |reason: String| {
+            violations.push(Violation {
+                metric: name.to_string(),
+                reason,
+            })
+        };
+
+        if !is_snake_case(name) {
+            violation("name must be lowercase snake_case".to_string());
+        }
+        if !self.prefixes.iter().any(|p| name.starts_with(p.as_str())) {
+            violation(format!(
+                "name must start with one of {}",
+                self.prefixes.join(", ")
+            ));
+        }
+
+        let field_type = family.get_field_type();
+        match (field_type == MetricType::COUNTER, name.ends_with("_total")) {
+            (true, false) => violation("counter names must end in _total".to_string()),
+            (false, true) => violation("only counters may end in _total".to_string()),
+            _ => {}
+        }
+
+        let is_eigen = name.starts_with(&format!("{NAMESPACE}_"));
+        for metric in family.get_metric() {
+            let labels: BTreeSet<&str> = metric.get_label().iter().map(|l| l.name()).collect();
+            for label in &labels {
+                if !is_snake_case(label) || label.starts_with("__") {
+                    violation(format!("label {label} must be lowercase snake_case"));
+                }
+            }
+            if is_eigen && !labels.contains("avs_name") {
+                violation("eigen_ metrics must carry an avs_name label".to_string());
+            }
+            if let Some((_, spec_type, spec_labels, _)) =
+                SPEC_METRICS.iter().find(|(spec, ..)| *spec == name)
+            {
+                if field_type != *spec_type {
+                    violation(format!(
+                        "spec requires type {spec_type:?}, found {field_type:?}"
+                    ));
+                }
+                let expected: BTreeSet<&str> = spec_labels.iter().copied().collect();
+                if labels != expected {
+                    violation(format!(
+                        "spec requires labels {expected:?}, found {labels:?}"
+                    ));
+                }
+            }
+        }
+        // Every label set of a family tends to repeat the same violation.
+        violations.dedup();
+    }
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
@@ -0,0 +1,67 @@
+//! Metrics every AVS node must expose according to the EigenLayer AVS node
+//! spec. All of them live under the `eigen` namespace and carry an
+//! `avs_name` const label so several AVSs can share one registry.
+
+use prometheus::{CounterVec, Gauge, Opts, Registry};
+
+pub const NAMESPACE: &str = "eigen";
+
+#[derive(Clone)]
+pub struct EigenMetrics {
+    fees_earned_total: CounterVec,
+    performance_score: Gauge,
+    registration_status: Gauge,
+}
+
+impl EigenMetrics {
+    pub fn new(avs_name: &str, registry: &Registry) -> prometheus::Result<Self> {
+        let opts = |name: &str, help: &str| {
+            Opts::new(name, help)
+                .namespace(NAMESPACE)
+                .const_label("avs_name", avs_name)
+        };
+
+        let fees_earned_total = CounterVec::new(
+            opts("fees_earned_total", "The amount of fees earned in <token>"),
+            &["token"],
+        )?;
+        let performance_score = Gauge::with_opts(opts(
+            "performance_score",
+            "The performance metric is a score between 0 and 100 and each developer can define their own way of calculating the score. The score is calculated based on the performance of the Node and the performance of the backing services.",
+        ))?;
+        let registration_status = Gauge::with_opts(opts(
+            "registration_status",
+            "Whether the operator is registered with the AVS (1) or not (0)",
+        ))?;
+
+        registry.register(Box::new(fees_earned_total.clone()))?;
+        registry.register(Box::new(performance_score.clone()))?;
+        registry.register(Box::new(registration_status.clone()))?;
+
+        Ok(Self {
+            fees_earned_total,
+            performance_score,
+            registration_status,
+        })
+    }
+
+    /// Amounts that aren't finite and non-negative are ignored, as counters
+    /// can only go up.
+    pub fn add_fee_earned(&self, token: &str, amount: f64) {
+        if !amount.is_finite() || amount < 0.0 {
+            return;
+        }
+        self.fees_earned_total
+            .with_label_values(&[token])
+            .inc_by(amount);
+    }
+
+    pub fn set_performance_score(&self, score: f64) {
+        self.performance_score.set(score);
+    }
+
+    pub fn set_registered(&self, registered: bool) {
+        self.registration_status
+            .set(if registered { 1.0 } else { 0.0 });
+    }
+}
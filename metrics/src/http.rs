@@ -0,0 +1,80 @@
+//! Per-route request metrics for axum servers such as the node API.
+
+use crate::eigenmetrics::NAMESPACE;
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use prometheus::{CounterVec, HistogramOpts, HistogramVec, Opts, Registry};
+use std::time::Instant;
+
+#[derive(Clone)]
+pub struct HttpMetrics {
+    requests_total: CounterVec,
+    request_duration_seconds: HistogramVec,
+}
+
+impl HttpMetrics {
+    pub fn new(avs_name: &str, registry: &Registry) -> prometheus::Result<Self> {
+        let labels = ["route", "method", "status"];
+        let requests_total = CounterVec::new(
+            Opts::new(
+                "http_requests_total",
+                "Total number of HTTP requests served",
+            )
+            .namespace(NAMESPACE)
+            .const_label("avs_name", avs_name),
+            &labels,
+        )?;
+        let request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Duration of HTTP requests in seconds",
+            )
+            .namespace(NAMESPACE)
+            .const_label("avs_name", avs_name),
+            &labels,
+        )?;
+
+        registry.register(Box::new(requests_total.clone()))?;
+        registry.register(Box::new(request_duration_seconds.clone()))?;
+
+        Ok(Self {
+            requests_total,
+            request_duration_seconds,
+        })
+    }
+
+    /// Records every request `router` serves. Call it after all routes are
+    /// added, since axum layers only wrap routes registered before them.
+    pub fn instrument<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        router.layer(middleware::from_fn_with_state(self.clone(), track))
+    }
+}
+
+async fn track(State(metrics): State<HttpMetrics>, request: Request, next: Next) -> Response {
+    // Label by route template so path parameters don't blow up cardinality.
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let status = response.status().as_u16().to_string();
+    let labels = [route.as_str(), method.as_str(), status.as_str()];
+    metrics.requests_total.with_label_values(&labels).inc();
+    metrics
+        .request_duration_seconds
+        .with_label_values(&labels)
+        .observe(elapsed);
+
+    response
+}
@@ -0,0 +1,13 @@
+pub mod conformance;
+pub mod eigenmetrics;
+pub mod http;
+#[cfg(feature = "otlp")]
+pub mod otlp;
+#[cfg(feature = "push")]
+pub mod push;
+pub mod quorum;
+pub mod rpccalls;
+pub mod runtime;
+pub mod server;
+
+pub use server::{metrics_router, start_metrics_server};
@@ -0,0 +1,223 @@
+//! Pushes a Prometheus registry to an OTLP/HTTP collector, for operators who
+//! run OpenTelemetry collectors instead of scraping `/metrics`.
+
+use logging::{SharedLogger, TracingLogger};
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, AggregationTemporality, Gauge, Histogram, HistogramDataPoint,
+    Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prometheus::proto::{LabelPair, MetricFamily, MetricType};
+use prometheus::Registry;
+use prost::Message;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, thiserror::Error)]
+pub enum OtlpError {
+    #[error("failed to send metrics to collector: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("collector rejected metrics with status {0}")]
+    Status(reqwest::StatusCode),
+}
+
+errors::error_codes!(OtlpError {
+    Http => ("metrics.otlp_unreachable", Network),
+    Status => ("metrics.otlp_rejected", Network),
+});
+
+pub struct OtlpExporter {
+    client: reqwest::Client,
+    url: String,
+    service_name: String,
+    registry: Registry,
+    interval: Duration,
+    start_time: SystemTime,
+    logger: SharedLogger,
+}
+
+impl OtlpExporter {
+    /// `endpoint` is the collector's OTLP/HTTP base URL, e.g. `http://localhost:4318`.
+    pub fn new(endpoint: &str, service_name: impl Into<String>, registry: Registry) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: format!("{}/v1/metrics", endpoint.trim_end_matches('/')),
+            service_name: service_name.into(),
+            registry,
+            interval: Duration::from_secs(15),
+            start_time: SystemTime::now(),
+            logger: Arc::new(TracingLogger),
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Defaults to [`TracingLogger`].
+    pub fn with_logger(mut self, logger: SharedLogger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    pub async fn export(&self) -> Result<(), OtlpError> {
+        let request = self.export_request(SystemTime::now());
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
+            .body(request.encode_to_vec())
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(OtlpError::Status(response.status()));
+        }
+        Ok(())
+    }
+
+    /// Exports every interval, forever. Failed exports are logged and retried
+    /// on the next tick.
+    pub async fn run(&self) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.export().await {
+                self.logger
+                    .warn("OTLP export failed", &[("url", &self.url), ("error", &err)]);
+            }
+        }
+    }
+
+    /// The request [`Self::export`] sends, with data points timestamped `now`.
+    pub fn export_request(&self, now: SystemTime) -> ExportMetricsServiceRequest {
+        let start = unix_nanos(self.start_time);
+        let now = unix_nanos(now);
+        let metrics = self
+            .registry
+            .gather()
+            .iter()
+            .filter_map(|family| convert_family(family, start, now))
+            .collect();
+
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![key_value("service.name", &self.service_name)],
+                    ..Default::default()
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: env!("CARGO_PKG_NAME").to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                        ..Default::default()
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+}
+
+fn convert_family(family: &MetricFamily, start: u64, now: u64) -> Option<Metric> {
+    let number_point = |labels: &[LabelPair], value: f64| NumberDataPoint {
+        attributes: labels
+            .iter()
+            .map(|l| key_value(l.name(), l.value()))
+            .collect(),
+        start_time_unix_nano: start,
+        time_unix_nano: now,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    };
+
+    let data = match family.get_field_type() {
+        MetricType::COUNTER => metric::Data::Sum(Sum {
+            data_points: family
+                .get_metric()
+                .iter()
+                .map(|m| number_point(m.get_label(), m.get_counter().value()))
+                .collect(),
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            is_monotonic: true,
+        }),
+        MetricType::GAUGE => metric::Data::Gauge(Gauge {
+            data_points: family
+                .get_metric()
+                .iter()
+                .map(|m| number_point(m.get_label(), m.get_gauge().value()))
+                .collect(),
+        }),
+        MetricType::UNTYPED => metric::Data::Gauge(Gauge {
+            data_points: family
+                .get_metric()
+                .iter()
+                .map(|m| number_point(m.get_label(), m.untyped.value()))
+                .collect(),
+        }),
+        MetricType::HISTOGRAM => metric::Data::Histogram(Histogram {
+            data_points: family
+                .get_metric()
+                .iter()
+                .map(|m| {
+                    let histogram = m.get_histogram();
+                    // Prometheus buckets are cumulative and include +Inf implicitly;
+                    // OTLP wants per-bucket counts with one more count than bounds.
+                    let buckets = histogram.get_bucket();
+                    let mut bucket_counts = Vec::with_capacity(buckets.len() + 1);
+                    let mut previous = 0;
+                    for bucket in buckets {
+                        bucket_counts.push(bucket.cumulative_count() - previous);
+                        previous = bucket.cumulative_count();
+                    }
+                    bucket_counts.push(histogram.get_sample_count() - previous);
+
+                    HistogramDataPoint {
+                        attributes: m
+                            .get_label()
+                            .iter()
+                            .map(|l| key_value(l.name(), l.value()))
+                            .collect(),
+                        start_time_unix_nano: start,
+                        time_unix_nano: now,
+                        count: histogram.get_sample_count(),
+                        sum: Some(histogram.get_sample_sum()),
+                        bucket_counts,
+                        explicit_bounds: buckets.iter().map(|b| b.upper_bound()).collect(),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+        }),
+        // The prometheus crate never produces summaries from its own metric types.
+        MetricType::SUMMARY => return None,
+    };
+
+    Some(Metric {
+        name: family.name().to_string(),
+        description: family.help().to_string(),
+        data: Some(data),
+        ..Default::default()
+    })
+}
+
+fn key_value(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_string())),
+        }),
+        ..Default::default()
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,100 @@
+//! Pushgateway support for short-lived processes (stake updates, reward
+//! claims) that exit before Prometheus would get to scrape them.
+
+use logging::{SharedLogger, TracingLogger};
+use prometheus::{BasicAuthentication, Registry};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct Pusher {
+    url: String,
+    job: String,
+    grouping: HashMap<String, String>,
+    basic_auth: Option<(String, String)>,
+    registry: Registry,
+    interval: Duration,
+    logger: SharedLogger,
+}
+
+impl Pusher {
+    pub fn new(url: impl Into<String>, job: impl Into<String>, registry: Registry) -> Self {
+        Self {
+            url: url.into(),
+            job: job.into(),
+            grouping: HashMap::new(),
+            basic_auth: None,
+            registry,
+            interval: Duration::from_secs(15),
+            logger: Arc::new(TracingLogger),
+        }
+    }
+
+    pub fn with_grouping(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.grouping.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.basic_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Defaults to [`TracingLogger`].
+    pub fn with_logger(mut self, logger: SharedLogger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// Replaces this job's metrics on the gateway with the current registry.
+    pub async fn push(&self) -> prometheus::Result<()> {
+        let pusher = self.clone();
+        // The prometheus push client is blocking.
+        tokio::task::spawn_blocking(move || {
+            prometheus::push_metrics(
+                &pusher.job,
+                pusher.grouping,
+                &pusher.url,
+                pusher.registry.gather(),
+                pusher
+                    .basic_auth
+                    .map(|(username, password)| BasicAuthentication { username, password }),
+            )
+        })
+        .await
+        .map_err(|err| prometheus::Error::Msg(err.to_string()))?
+    }
+
+    /// Pushes every interval until `shutdown` resolves, then pushes once more so
+    /// the final values of a finishing job are not lost. Failed interval pushes
+    /// are logged and retried on the next tick; only the final push's error is
+    /// returned.
+    pub async fn run_until(&self, shutdown: impl Future<Output = ()>) -> prometheus::Result<()> {
+        tokio::pin!(shutdown);
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(err) = self.push().await {
+                        self.logger.warn(
+                            "pushgateway push failed",
+                            &[("url", &self.url), ("error", &err)],
+                        );
+                    }
+                }
+                _ = &mut shutdown => return self.push().await,
+            }
+        }
+    }
+}
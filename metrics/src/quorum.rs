@@ -0,0 +1,82 @@
+//! Per-quorum stake and registration gauges for the local operator, so alerts
+//! can fire when it drops below a quorum's minimum stake or gets ejected.
+
+use crate::eigenmetrics::NAMESPACE;
+use prometheus::{GaugeVec, Opts, Registry};
+use std::collections::HashMap;
+use types::QuorumNum;
+
+#[derive(Clone)]
+pub struct QuorumMetrics {
+    quorum_names: HashMap<QuorumNum, String>,
+    registered_stakes: GaugeVec,
+    minimum_stakes: GaugeVec,
+    registration_status: GaugeVec,
+}
+
+impl QuorumMetrics {
+    /// `quorum_names` fills the `quorum_name` label; unnamed quorums get an empty name.
+    pub fn new(
+        avs_name: &str,
+        quorum_names: HashMap<QuorumNum, String>,
+        registry: &Registry,
+    ) -> prometheus::Result<Self> {
+        let gauge = |name: &str, help: &str| {
+            GaugeVec::new(
+                Opts::new(name, help)
+                    .namespace(NAMESPACE)
+                    .const_label("avs_name", avs_name),
+                &["quorum_number", "quorum_name"],
+            )
+        };
+
+        let registered_stakes = gauge(
+            "registered_stakes",
+            "Operator stake in <quorum> of <avs_name>'s StakeRegistry contract",
+        )?;
+        let minimum_stakes = gauge(
+            "quorum_minimum_stake",
+            "Minimum stake required to register in <quorum> of <avs_name>",
+        )?;
+        let registration_status = gauge(
+            "quorum_registration_status",
+            "Whether the operator is registered in <quorum> of <avs_name> (1) or not (0)",
+        )?;
+
+        registry.register(Box::new(registered_stakes.clone()))?;
+        registry.register(Box::new(minimum_stakes.clone()))?;
+        registry.register(Box::new(registration_status.clone()))?;
+
+        Ok(Self {
+            quorum_names,
+            registered_stakes,
+            minimum_stakes,
+            registration_status,
+        })
+    }
+
+    pub fn set_registered_stake(&self, quorum: QuorumNum, stake: f64) {
+        self.registered_stakes
+            .with_label_values(&self.labels(quorum))
+            .set(stake);
+    }
+
+    pub fn set_minimum_stake(&self, quorum: QuorumNum, stake: f64) {
+        self.minimum_stakes
+            .with_label_values(&self.labels(quorum))
+            .set(stake);
+    }
+
+    pub fn set_registered(&self, quorum: QuorumNum, registered: bool) {
+        self.registration_status
+            .with_label_values(&self.labels(quorum))
+            .set(if registered { 1.0 } else { 0.0 });
+    }
+
+    fn labels(&self, quorum: QuorumNum) -> [String; 2] {
+        [
+            quorum.to_string(),
+            self.quorum_names.get(&quorum).cloned().unwrap_or_default(),
+        ]
+    }
+}
@@ -0,0 +1,71 @@
+//! Per-method RPC call metrics for chain clients.
+
+use crate::eigenmetrics::NAMESPACE;
+use prometheus::{CounterVec, HistogramOpts, HistogramVec, Opts, Registry};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+#[derive(Clone)]
+pub struct RpcCallsCollector {
+    request_duration_seconds: HistogramVec,
+    request_total: CounterVec,
+}
+
+impl RpcCallsCollector {
+    pub fn new(avs_name: &str, registry: &Registry) -> prometheus::Result<Self> {
+        let request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "rpc_request_duration_seconds",
+                "Duration of json-rpc <method> in seconds",
+            )
+            .namespace(NAMESPACE)
+            .const_label("avs_name", avs_name),
+            &["client", "method"],
+        )?;
+        let request_total = CounterVec::new(
+            Opts::new(
+                "rpc_request_total",
+                "Total number of json-rpc <method> requests",
+            )
+            .namespace(NAMESPACE)
+            .const_label("avs_name", avs_name),
+            &["client", "method", "status"],
+        )?;
+
+        registry.register(Box::new(request_duration_seconds.clone()))?;
+        registry.register(Box::new(request_total.clone()))?;
+
+        Ok(Self {
+            request_duration_seconds,
+            request_total,
+        })
+    }
+
+    pub fn observe_request_duration(&self, client: &str, method: &str, duration: Duration) {
+        self.request_duration_seconds
+            .with_label_values(&[client, method])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// `status` is `"success"` or `"error"` when recorded through [`Self::track`].
+    pub fn add_request(&self, client: &str, method: &str, status: &str) {
+        self.request_total
+            .with_label_values(&[client, method, status])
+            .inc();
+    }
+
+    /// Awaits `call`, recording its duration and whether it failed.
+    pub async fn track<T, E>(
+        &self,
+        client: &str,
+        method: &str,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let start = Instant::now();
+        let result = call.await;
+        self.observe_request_duration(client, method, start.elapsed());
+        let status = if result.is_ok() { "success" } else { "error" };
+        self.add_request(client, method, status);
+        result
+    }
+}
@@ -0,0 +1,160 @@
+//! Process and tokio runtime metrics, so AVS slowdowns can be correlated with
+//! CPU, memory or executor saturation.
+
+use crate::eigenmetrics::NAMESPACE;
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{CounterVec, IntCounterVec, IntGauge, Opts, Registry};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::runtime::Handle;
+
+/// Registers the standard `process_*` metrics (CPU seconds, resident memory,
+/// open fds, ...) for the current process.
+#[cfg(all(feature = "process", target_os = "linux"))]
+pub fn register_process_collector(registry: &Registry) -> prometheus::Result<()> {
+    registry.register(Box::new(
+        prometheus::process_collector::ProcessCollector::for_self(),
+    ))
+}
+
+/// Samples a tokio runtime's metrics every time the registry is gathered.
+///
+/// `rate(eigen_tokio_worker_busy_seconds_total[1m])` gives per-worker
+/// utilization. Blocking pool metrics are only available when built with
+/// `--cfg tokio_unstable`.
+pub struct RuntimeCollector {
+    handle: Handle,
+    workers: IntGauge,
+    alive_tasks: IntGauge,
+    global_queue_depth: IntGauge,
+    worker_busy_seconds: CounterVec,
+    worker_parks: IntCounterVec,
+    // Last sampled (busy duration, park count) per worker, to turn tokio's
+    // running totals into counter increments.
+    last: Mutex<Vec<(Duration, u64)>>,
+    #[cfg(tokio_unstable)]
+    blocking_threads: IntGauge,
+    #[cfg(tokio_unstable)]
+    blocking_queue_depth: IntGauge,
+}
+
+impl RuntimeCollector {
+    pub fn new(avs_name: &str, handle: Handle) -> prometheus::Result<Self> {
+        let opts = |name: &str, help: &str| {
+            Opts::new(name, help)
+                .namespace(NAMESPACE)
+                .subsystem("tokio")
+                .const_label("avs_name", avs_name)
+        };
+
+        Ok(Self {
+            workers: IntGauge::with_opts(opts("workers", "Number of runtime worker threads"))?,
+            alive_tasks: IntGauge::with_opts(opts(
+                "alive_tasks",
+                "Number of tasks currently alive in the runtime",
+            ))?,
+            global_queue_depth: IntGauge::with_opts(opts(
+                "global_queue_depth",
+                "Number of tasks waiting in the runtime's global queue",
+            ))?,
+            worker_busy_seconds: CounterVec::new(
+                opts(
+                    "worker_busy_seconds_total",
+                    "Time each worker thread has spent busy",
+                ),
+                &["worker"],
+            )?,
+            worker_parks: IntCounterVec::new(
+                opts(
+                    "worker_park_total",
+                    "Number of times each worker thread has parked",
+                ),
+                &["worker"],
+            )?,
+            last: Mutex::new(vec![]),
+            #[cfg(tokio_unstable)]
+            blocking_threads: IntGauge::with_opts(opts(
+                "blocking_threads",
+                "Number of threads in the blocking pool",
+            ))?,
+            #[cfg(tokio_unstable)]
+            blocking_queue_depth: IntGauge::with_opts(opts(
+                "blocking_queue_depth",
+                "Number of tasks waiting for a blocking pool thread",
+            ))?,
+            handle,
+        })
+    }
+
+    /// Builds a collector for the runtime the caller is running on and
+    /// registers it. Panics outside a tokio runtime.
+    pub fn register(avs_name: &str, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(Self::new(avs_name, Handle::current())?))
+    }
+
+    fn sample(&self) {
+        let metrics = self.handle.metrics();
+        let workers = metrics.num_workers();
+        self.workers.set(workers as i64);
+        self.alive_tasks.set(metrics.num_alive_tasks() as i64);
+        self.global_queue_depth
+            .set(metrics.global_queue_depth() as i64);
+        #[cfg(tokio_unstable)]
+        {
+            self.blocking_threads
+                .set(metrics.num_blocking_threads() as i64);
+            self.blocking_queue_depth
+                .set(metrics.blocking_queue_depth() as i64);
+        }
+
+        let mut last = self.last.lock().unwrap();
+        last.resize(workers, (Duration::ZERO, 0));
+        for (worker, (last_busy, last_parks)) in last.iter_mut().enumerate() {
+            let label = worker.to_string();
+            let busy = metrics.worker_total_busy_duration(worker);
+            let parks = metrics.worker_park_count(worker);
+            self.worker_busy_seconds
+                .with_label_values(&[&label])
+                .inc_by(busy.saturating_sub(*last_busy).as_secs_f64());
+            self.worker_parks
+                .with_label_values(&[&label])
+                .inc_by(parks.saturating_sub(*last_parks));
+            *last_busy = busy;
+            *last_parks = parks;
+        }
+    }
+}
+
+impl Collector for RuntimeCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        let mut descs = vec![];
+        descs.extend(self.workers.desc());
+        descs.extend(self.alive_tasks.desc());
+        descs.extend(self.global_queue_depth.desc());
+        descs.extend(self.worker_busy_seconds.desc());
+        descs.extend(self.worker_parks.desc());
+        #[cfg(tokio_unstable)]
+        {
+            descs.extend(self.blocking_threads.desc());
+            descs.extend(self.blocking_queue_depth.desc());
+        }
+        descs
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.sample();
+        let mut families = vec![];
+        families.extend(self.workers.collect());
+        families.extend(self.alive_tasks.collect());
+        families.extend(self.global_queue_depth.collect());
+        families.extend(self.worker_busy_seconds.collect());
+        families.extend(self.worker_parks.collect());
+        #[cfg(tokio_unstable)]
+        {
+            families.extend(self.blocking_threads.collect());
+            families.extend(self.blocking_queue_depth.collect());
+        }
+        families
+    }
+}
@@ -0,0 +1,34 @@
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::{routing::get, Extension, Router};
+use prometheus::{Encoder, Registry, TextEncoder};
+use std::net::SocketAddr;
+
+/// Router serving `registry` on `/metrics`, for merging into an existing server.
+pub fn metrics_router(registry: Registry) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .layer(Extension(registry))
+}
+
+pub async fn start_metrics_server(addr: SocketAddr, registry: Registry) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, metrics_router(registry)).await
+}
+
+async fn metrics_handler(Extension(registry): Extension<Registry>) -> impl IntoResponse {
+    let encoder = TextEncoder::new();
+    let mut body = vec![];
+    match encoder.encode(&registry.gather(), &mut body) {
+        Ok(()) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, encoder.format_type().to_string())],
+            body,
+        ),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            [(header::CONTENT_TYPE, "text/plain".to_string())],
+            err.to_string().into_bytes(),
+        ),
+    }
+}
@@ -0,0 +1,119 @@
+use metrics::conformance::{ConformanceChecker, Violation};
+use metrics::eigenmetrics::EigenMetrics;
+use metrics::http::HttpMetrics;
+use metrics::quorum::QuorumMetrics;
+use metrics::rpccalls::RpcCallsCollector;
+use metrics::runtime::RuntimeCollector;
+use prometheus::{Counter, Gauge, Opts, Registry};
+use std::time::Duration;
+use types::QuorumNum;
+
+fn reasons(violations: &[Violation], metric: &str) -> Vec<String> {
+    violations
+        .iter()
+        .filter(|v| v.metric == metric)
+        .map(|v| v.reason.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn sdk_metrics_conform() {
+    let registry = Registry::new();
+    let eigen = EigenMetrics::new("avs", &registry).unwrap();
+    let quorum = QuorumMetrics::new("avs", Default::default(), &registry).unwrap();
+    let rpc = RpcCallsCollector::new("avs", &registry).unwrap();
+    HttpMetrics::new("avs", &registry).unwrap();
+    RuntimeCollector::register("avs", &registry).unwrap();
+
+    // Labelled metrics only show up once recorded.
+    eigen.add_fee_earned("ETH", 1.0);
+    eigen.set_performance_score(100.0);
+    quorum.set_registered_stake(QuorumNum(0), 32.0);
+    quorum.set_minimum_stake(QuorumNum(0), 1.0);
+    quorum.set_registered(QuorumNum(0), true);
+    rpc.observe_request_duration("eth", "eth_call", Duration::from_millis(5));
+    rpc.add_request("eth", "eth_call", "success");
+
+    ConformanceChecker::new()
+        .require_spec_metrics()
+        .assert_conformant(&registry);
+}
+
+#[test]
+fn rejects_bad_names() {
+    let registry = Registry::new();
+    registry
+        .register(Box::new(
+            Gauge::with_opts(Opts::new("eigen_Performance", "help").const_label("avs_name", "avs"))
+                .unwrap(),
+        ))
+        .unwrap();
+    registry
+        .register(Box::new(
+            Counter::with_opts(Opts::new("myavs_tasks", "help")).unwrap(),
+        ))
+        .unwrap();
+
+    let violations = ConformanceChecker::new().check(&registry);
+    assert_eq!(
+        reasons(&violations, "eigen_Performance"),
+        ["name must be lowercase snake_case"]
+    );
+    let reasons = reasons(&violations, "myavs_tasks");
+    assert!(reasons.contains(&"counter names must end in _total".to_string()));
+    assert!(reasons
+        .iter()
+        .any(|r| r.starts_with("name must start with one of")));
+
+    let allowed = ConformanceChecker::new()
+        .allow_prefix("myavs_")
+        .check(&registry);
+    assert!(!allowed
+        .iter()
+        .any(|v| v.reason.starts_with("name must start with")));
+}
+
+#[test]
+fn requires_avs_name_on_eigen_metrics() {
+    let registry = Registry::new();
+    registry
+        .register(Box::new(
+            Gauge::with_opts(Opts::new("eigen_performance_score", "help")).unwrap(),
+        ))
+        .unwrap();
+
+    let violations = ConformanceChecker::new().check(&registry);
+    let reasons = reasons(&violations, "eigen_performance_score");
+    assert!(reasons.contains(&"eigen_ metrics must carry an avs_name label".to_string()));
+    // The spec label set for this metric is {avs_name} too.
+    assert!(reasons
+        .iter()
+        .any(|r| r.starts_with("spec requires labels")));
+}
+
+#[test]
+fn reports_missing_spec_metrics() {
+    let registry = Registry::new();
+    assert!(ConformanceChecker::new().check(&registry).is_empty());
+
+    let violations = ConformanceChecker::new()
+        .require_spec_metrics()
+        .check(&registry);
+    let missing: Vec<_> = violations.iter().map(|v| v.metric.as_str()).collect();
+    assert_eq!(
+        missing,
+        ["eigen_fees_earned_total", "eigen_performance_score"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "metrics spec violation")]
+fn assert_conformant_panics() {
+    let registry = Registry::new();
+    registry
+        .register(Box::new(
+            Gauge::with_opts(Opts::new("Bad", "help")).unwrap(),
+        ))
+        .unwrap();
+    ConformanceChecker::new().assert_conformant(&registry);
+}
@@ -0,0 +1,85 @@
+use metrics::eigenmetrics::EigenMetrics;
+use prometheus::proto::MetricFamily;
+use prometheus::Registry;
+
+fn family(registry: &Registry, name: &str) -> MetricFamily {
+    registry
+        .gather()
+        .into_iter()
+        .find(|f| f.name() == name)
+        .unwrap_or_else(|| panic!("{name} not gathered"))
+}
+
+fn labels(family: &MetricFamily) -> Vec<Vec<(String, String)>> {
+    family
+        .get_metric()
+        .iter()
+        .map(|m| {
+            m.get_label()
+                .iter()
+                .map(|l| (l.name().to_string(), l.value().to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+fn label(name: &str, value: &str) -> (String, String) {
+    (name.to_string(), value.to_string())
+}
+
+#[test]
+fn records_fees_per_token() {
+    let registry = Registry::new();
+    let metrics = EigenMetrics::new("avs", &registry).unwrap();
+    metrics.add_fee_earned("ETH", 1.5);
+    metrics.add_fee_earned("ETH", 2.0);
+    metrics.add_fee_earned("USDC", 10.0);
+
+    let fees = family(&registry, "eigen_fees_earned_total");
+    assert_eq!(
+        labels(&fees),
+        [
+            [label("avs_name", "avs"), label("token", "ETH")],
+            [label("avs_name", "avs"), label("token", "USDC")],
+        ]
+    );
+    let values: Vec<_> = fees
+        .get_metric()
+        .iter()
+        .map(|m| m.get_counter().value())
+        .collect();
+    assert_eq!(values, [3.5, 10.0]);
+}
+
+#[test]
+fn ignores_invalid_fee_amounts() {
+    let registry = Registry::new();
+    let metrics = EigenMetrics::new("avs", &registry).unwrap();
+    metrics.add_fee_earned("ETH", 1.0);
+    for amount in [-1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        metrics.add_fee_earned("ETH", amount);
+    }
+    let fees = family(&registry, "eigen_fees_earned_total");
+    assert_eq!(fees.get_metric()[0].get_counter().value(), 1.0);
+}
+
+#[test]
+fn sets_score_and_registration() {
+    let registry = Registry::new();
+    let metrics = EigenMetrics::new("avs", &registry).unwrap();
+    metrics.set_performance_score(87.5);
+    metrics.set_registered(true);
+
+    for (name, value) in [
+        ("eigen_performance_score", 87.5),
+        ("eigen_registration_status", 1.0),
+    ] {
+        let family = family(&registry, name);
+        assert_eq!(labels(&family), [[label("avs_name", "avs")]], "{name}");
+        assert_eq!(family.get_metric()[0].get_gauge().value(), value);
+    }
+
+    metrics.set_registered(false);
+    let status = family(&registry, "eigen_registration_status");
+    assert_eq!(status.get_metric()[0].get_gauge().value(), 0.0);
+}
@@ -0,0 +1,84 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use metrics::http::HttpMetrics;
+use prometheus::proto::Metric;
+use prometheus::Registry;
+use std::time::Duration;
+use tower::ServiceExt;
+
+/// `name`'s metrics, keyed by their `route,method,status` labels.
+fn metrics(registry: &Registry, name: &str) -> Vec<(String, Metric)> {
+    registry
+        .gather()
+        .into_iter()
+        .filter(|f| f.name() == name)
+        .flat_map(|f| f.get_metric().to_vec())
+        .map(|m| {
+            let value = |name: &str| {
+                m.get_label()
+                    .iter()
+                    .find(|l| l.name() == name)
+                    .map(|l| l.value().to_string())
+                    .unwrap()
+            };
+            let key = format!("{},{},{}", value("route"), value("method"), value("status"));
+            (key, m)
+        })
+        .collect()
+}
+
+async fn request(router: &Router, uri: &str) -> StatusCode {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn records_requests_per_route_template() {
+    let registry = Registry::new();
+    let http = HttpMetrics::new("avs", &registry).unwrap();
+    let router = http.instrument(
+        Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    "done"
+                }),
+            )
+            .route(
+                "/services/:id/health",
+                get(|| async { StatusCode::SERVICE_UNAVAILABLE }),
+            ),
+    );
+
+    assert_eq!(request(&router, "/slow").await, StatusCode::OK);
+    for id in ["a", "b"] {
+        let uri = format!("/services/{id}/health");
+        assert_eq!(
+            request(&router, &uri).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+    assert_eq!(request(&router, "/missing").await, StatusCode::NOT_FOUND);
+
+    let counts: Vec<_> = metrics(&registry, "eigen_http_requests_total")
+        .into_iter()
+        .map(|(key, m)| (key, m.get_counter().value()))
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            ("/services/:id/health,GET,503".to_string(), 2.0),
+            ("/slow,GET,200".to_string(), 1.0),
+            ("unmatched,GET,404".to_string(), 1.0),
+        ]
+    );
+
+    let durations = metrics(&registry, "eigen_http_request_duration_seconds");
+    let (key, slow) = &durations[1];
+    assert_eq!(key, "/slow,GET,200");
+    assert_eq!(slow.get_histogram().get_sample_count(), 1);
+    assert!(slow.get_histogram().get_sample_sum() >= 0.02);
+}
@@ -0,0 +1,169 @@
+use axum::body::Bytes;
+use axum::http::StatusCode;
+use axum::Router;
+use errors::{EigenError, ErrorCategory};
+use logging::{Fields, Level, Logger};
+use metrics::otlp::{OtlpError, OtlpExporter};
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, AggregationTemporality, Metric,
+};
+use prometheus::{CounterVec, Gauge, HistogramOpts, HistogramVec, Opts, Registry};
+use prost::Message;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+fn metric<'a>(request: &'a ExportMetricsServiceRequest, name: &str) -> &'a Metric {
+    request.resource_metrics[0].scope_metrics[0]
+        .metrics
+        .iter()
+        .find(|m| m.name == name)
+        .unwrap_or_else(|| panic!("{name} not exported"))
+}
+
+fn attributes(attributes: &[KeyValue]) -> Vec<(&str, &str)> {
+    attributes
+        .iter()
+        .map(|kv| match &kv.value.as_ref().unwrap().value {
+            Some(any_value::Value::StringValue(value)) => (kv.key.as_str(), value.as_str()),
+            value => panic!("{}: unexpected value {value:?}", kv.key),
+        })
+        .collect()
+}
+
+#[test]
+fn converts_counters_and_gauges() {
+    let registry = Registry::new();
+    let fees = CounterVec::new(Opts::new("fees_total", "fees"), &["token"]).unwrap();
+    fees.with_label_values(&["ETH"]).inc_by(2.5);
+    let score = Gauge::new("score", "score").unwrap();
+    score.set(87.0);
+    registry.register(Box::new(fees)).unwrap();
+    registry.register(Box::new(score)).unwrap();
+
+    let exporter = OtlpExporter::new("http://collector", "my-avs", registry);
+    let request = exporter.export_request(SystemTime::now());
+    let resource = request.resource_metrics[0].resource.as_ref().unwrap();
+    assert_eq!(
+        attributes(&resource.attributes),
+        [("service.name", "my-avs")]
+    );
+
+    let fees = metric(&request, "fees_total");
+    assert_eq!(fees.description, "fees");
+    let Some(metric::Data::Sum(sum)) = &fees.data else {
+        panic!("counter must export as a sum: {fees:?}");
+    };
+    assert!(sum.is_monotonic);
+    assert_eq!(
+        sum.aggregation_temporality,
+        AggregationTemporality::Cumulative as i32
+    );
+    let point = &sum.data_points[0];
+    assert_eq!(attributes(&point.attributes), [("token", "ETH")]);
+    assert_eq!(point.value, Some(number_data_point::Value::AsDouble(2.5)));
+    assert!(point.start_time_unix_nano <= point.time_unix_nano);
+
+    let Some(metric::Data::Gauge(gauge)) = &metric(&request, "score").data else {
+        panic!("gauge must export as a gauge");
+    };
+    assert!(gauge.data_points[0].attributes.is_empty());
+    assert_eq!(
+        gauge.data_points[0].value,
+        Some(number_data_point::Value::AsDouble(87.0))
+    );
+}
+
+#[test]
+fn converts_cumulative_buckets_to_per_bucket_counts() {
+    let registry = Registry::new();
+    let latency = HistogramVec::new(
+        HistogramOpts::new("latency_seconds", "latency").buckets(vec![1.0, 2.0, 5.0]),
+        &["route"],
+    )
+    .unwrap();
+    for value in [0.5, 1.5, 1.7, 3.0, 10.0, 20.0] {
+        latency.with_label_values(&["/node"]).observe(value);
+    }
+    registry.register(Box::new(latency)).unwrap();
+
+    let request =
+        OtlpExporter::new("http://collector", "avs", registry).export_request(SystemTime::now());
+    let Some(metric::Data::Histogram(histogram)) = &metric(&request, "latency_seconds").data else {
+        panic!("histogram must export as a histogram");
+    };
+    assert_eq!(
+        histogram.aggregation_temporality,
+        AggregationTemporality::Cumulative as i32
+    );
+    let point = &histogram.data_points[0];
+    assert_eq!(attributes(&point.attributes), [("route", "/node")]);
+    assert_eq!(point.explicit_bounds, [1.0, 2.0, 5.0]);
+    // The last count is the implicit +Inf bucket.
+    assert_eq!(point.bucket_counts, [1, 2, 1, 2]);
+    assert_eq!(point.count, 6);
+    assert_eq!(point.sum, Some(36.7));
+}
+
+#[derive(Default)]
+struct CountingLogger(Mutex<Vec<String>>);
+
+impl Logger for CountingLogger {
+    fn log(&self, level: Level, msg: &str, _: Fields<'_>) {
+        if level == Level::Warn {
+            self.0.lock().unwrap().push(msg.to_string());
+        }
+    }
+}
+
+#[tokio::test]
+async fn run_keeps_exporting_after_failures() {
+    let exports = Arc::new(AtomicU32::new(0));
+    let counted = exports.clone();
+    let router = Router::new().fallback(move |body: Bytes| {
+        let export = counted.fetch_add(1, Ordering::SeqCst);
+        async move {
+            ExportMetricsServiceRequest::decode(body).unwrap();
+            if export < 2 {
+                StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                StatusCode::OK
+            }
+        }
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let logger = Arc::new(CountingLogger::default());
+    let exporter = OtlpExporter::new(&url, "avs", Registry::new())
+        .with_interval(Duration::from_millis(10))
+        .with_logger(logger.clone());
+    let run = tokio::spawn(async move { exporter.run().await });
+    while exports.load(Ordering::SeqCst) < 4 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert!(!run.is_finished());
+    run.abort();
+
+    assert_eq!(*logger.0.lock().unwrap(), ["OTLP export failed"; 2]);
+}
+
+#[tokio::test]
+async fn rejected_exports_carry_codes() {
+    let router = Router::new().fallback(|| async { StatusCode::BAD_REQUEST });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let exporter = OtlpExporter::new(&url, "avs", Registry::new());
+    let err = EigenError::from(exporter.export().await.unwrap_err());
+    assert_eq!(err.code(), "metrics.otlp_rejected");
+    assert_eq!(err.category(), ErrorCategory::Network);
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(OtlpError::Status(StatusCode::BAD_REQUEST))
+    ));
+}
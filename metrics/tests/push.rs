@@ -0,0 +1,61 @@
+use axum::http::StatusCode;
+use axum::Router;
+use logging::NoopLogger;
+use metrics::push::Pusher;
+use prometheus::{IntCounter, Registry};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A Pushgateway stand-in that rejects the first `failures` pushes.
+async fn gateway(failures: u32) -> (String, Arc<AtomicU32>) {
+    let pushes = Arc::new(AtomicU32::new(0));
+    let counted = pushes.clone();
+    let router = Router::new().fallback(move || {
+        let push = counted.fetch_add(1, Ordering::SeqCst);
+        async move {
+            if push < failures {
+                StatusCode::INTERNAL_SERVER_ERROR
+            } else {
+                StatusCode::OK
+            }
+        }
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    (url, pushes)
+}
+
+fn registry() -> Registry {
+    let registry = Registry::new();
+    let counter = IntCounter::new("eigen_test_total", "test").unwrap();
+    counter.inc();
+    registry.register(Box::new(counter)).unwrap();
+    registry
+}
+
+#[tokio::test]
+async fn interval_failures_do_not_stop_the_final_push() {
+    let (url, pushes) = gateway(2).await;
+    let pusher = Pusher::new(url, "job", registry())
+        .with_interval(Duration::from_millis(10))
+        .with_logger(Arc::new(NoopLogger));
+
+    let shutdown = async {
+        while pushes.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    };
+    pusher.run_until(shutdown).await.unwrap();
+    assert!(pushes.load(Ordering::SeqCst) >= 4);
+}
+
+#[tokio::test]
+async fn final_push_error_is_returned() {
+    let (url, _) = gateway(u32::MAX).await;
+    let pusher = Pusher::new(url, "job", registry())
+        .with_interval(Duration::from_secs(3600))
+        .with_logger(Arc::new(NoopLogger));
+    assert!(pusher.run_until(async {}).await.is_err());
+}
@@ -0,0 +1,82 @@
+use metrics::rpccalls::RpcCallsCollector;
+use prometheus::proto::Metric;
+use prometheus::Registry;
+use std::time::Duration;
+
+/// `name`'s metrics, keyed by their labels other than `avs_name`.
+fn metrics(registry: &Registry, name: &str) -> Vec<(Vec<String>, Metric)> {
+    registry
+        .gather()
+        .into_iter()
+        .filter(|f| f.name() == name)
+        .flat_map(|f| f.get_metric().to_vec())
+        .map(|m| {
+            let labels = m
+                .get_label()
+                .iter()
+                .filter(|l| l.name() != "avs_name")
+                .map(|l| format!("{}={}", l.name(), l.value()))
+                .collect();
+            (labels, m)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn tracks_successful_and_failed_calls() {
+    let registry = Registry::new();
+    let rpc = RpcCallsCollector::new("avs", &registry).unwrap();
+
+    let ok: Result<u64, String> = rpc
+        .track("geth/v1.14", "eth_blockNumber", async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(7)
+        })
+        .await;
+    assert_eq!(ok, Ok(7));
+    let err: Result<u64, String> = rpc
+        .track("geth/v1.14", "eth_call", async {
+            Err("reverted".to_string())
+        })
+        .await;
+    assert_eq!(err, Err("reverted".to_string()));
+    rpc.track("geth/v1.14", "eth_call", async { Ok::<_, ()>(()) })
+        .await
+        .unwrap();
+
+    let durations = metrics(&registry, "eigen_rpc_request_duration_seconds");
+    let counts: Vec<_> = durations
+        .iter()
+        .map(|(labels, m)| (labels.join(","), m.get_histogram().get_sample_count()))
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            ("client=geth/v1.14,method=eth_blockNumber".to_string(), 1),
+            ("client=geth/v1.14,method=eth_call".to_string(), 2),
+        ]
+    );
+    assert!(durations[0].1.get_histogram().get_sample_sum() >= 0.02);
+
+    let requests: Vec<_> = metrics(&registry, "eigen_rpc_request_total")
+        .into_iter()
+        .map(|(labels, m)| (labels.join(","), m.get_counter().value()))
+        .collect();
+    assert_eq!(
+        requests,
+        [
+            (
+                "client=geth/v1.14,method=eth_blockNumber,status=success".to_string(),
+                1.0
+            ),
+            (
+                "client=geth/v1.14,method=eth_call,status=error".to_string(),
+                1.0
+            ),
+            (
+                "client=geth/v1.14,method=eth_call,status=success".to_string(),
+                1.0
+            ),
+        ]
+    );
+}
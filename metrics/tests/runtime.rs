@@ -0,0 +1,71 @@
+use metrics::runtime::RuntimeCollector;
+use prometheus::Registry;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Every sample of every family, keyed by family name and `worker` label.
+fn sample(registry: &Registry) -> BTreeMap<(String, String), f64> {
+    let mut samples = BTreeMap::new();
+    for family in registry.gather() {
+        for metric in family.get_metric() {
+            let worker = metric
+                .get_label()
+                .iter()
+                .find(|l| l.name() == "worker")
+                .map(|l| l.value().to_string())
+                .unwrap_or_default();
+            let value = if metric.get_counter().is_some() {
+                metric.get_counter().value()
+            } else {
+                metric.get_gauge().value()
+            };
+            samples.insert((family.name().to_string(), worker), value);
+        }
+    }
+    samples
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn samples_the_current_runtime() {
+    let registry = Registry::new();
+    RuntimeCollector::register("avs", &registry).unwrap();
+
+    let first = sample(&registry);
+    let value = |samples: &BTreeMap<_, _>, name: &str, worker: &str| {
+        samples[&(name.to_string(), worker.to_string())]
+    };
+    assert_eq!(value(&first, "eigen_tokio_workers", ""), 2.0);
+    assert!(value(&first, "eigen_tokio_alive_tasks", "") >= 0.0);
+    assert!(value(&first, "eigen_tokio_global_queue_depth", "") >= 0.0);
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            tokio::spawn(async {
+                for _ in 0..10 {
+                    std::thread::sleep(Duration::from_millis(1));
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let second = sample(&registry);
+    let mut busy = 0.0;
+    for worker in ["0", "1"] {
+        for name in [
+            "eigen_tokio_worker_busy_seconds_total",
+            "eigen_tokio_worker_park_total",
+        ] {
+            assert!(
+                value(&second, name, worker) >= value(&first, name, worker),
+                "{name} went down on worker {worker}"
+            );
+        }
+        busy += value(&second, "eigen_tokio_worker_busy_seconds_total", worker);
+    }
+    // The tasks block their workers for at least 80ms in total.
+    assert!(busy >= 0.08, "busy {busy}s");
+}
@@ -0,0 +1,57 @@
+use metrics::{metrics_router, start_metrics_server};
+use prometheus::{IntCounter, Registry};
+
+#[tokio::test]
+async fn serves_registered_metrics() {
+    let registry = Registry::new();
+    let counter = IntCounter::new("eigen_test_requests_total", "test").unwrap();
+    counter.inc_by(3);
+    registry.register(Box::new(counter)).unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, metrics_router(registry))
+            .await
+            .unwrap()
+    });
+
+    let response = reqwest::get(format!("http://{addr}/metrics"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; version=0.0.4"
+    );
+    let body = response.text().await.unwrap();
+    assert!(
+        body.lines()
+            .any(|line| line == "eigen_test_requests_total 3"),
+        "{body}"
+    );
+
+    let missing = reqwest::get(format!("http://{addr}/other")).await.unwrap();
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn start_metrics_server_serves_and_reports_bind_errors() {
+    let taken = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = taken.local_addr().unwrap();
+    let err = start_metrics_server(addr, Registry::new())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+
+    drop(taken);
+    tokio::spawn(start_metrics_server(addr, Registry::new()));
+    for _ in 0..50 {
+        if let Ok(response) = reqwest::get(format!("http://{addr}/metrics")).await {
+            assert_eq!(response.status(), reqwest::StatusCode::OK);
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("metrics server never came up on {addr}");
+}
@@ -0,0 +1,121 @@
+//! Container healthcheck probe for distroless images without curl:
+//!
+//! ```text
+//! HEALTHCHECK CMD ["nodeapi-healthcheck", "--addr", "127.0.0.1:9010"]
+//! ```
+//!
+//! Exits 0 when `/node/health` reports Healthy, and 1 when it reports
+//! Unhealthy or can't be reached. PartiallyHealthy exits 0 unless `--strict`
+//! is passed. Docker reserves exit code 2, so there's no separate code for it.
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "usage: nodeapi-healthcheck [--addr HOST:PORT] [--timeout SECS] [--strict]
+
+--addr defaults to $EIGEN_NODEAPI_ADDR, then 127.0.0.1:3000.";
+
+struct Args {
+    addr: String,
+    timeout: Duration,
+    strict: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        addr: std::env::var("EIGEN_NODEAPI_ADDR").unwrap_or_else(|_| "127.0.0.1:3000".to_string()),
+        timeout: Duration::from_secs(3),
+        strict: false,
+    };
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "--addr" => args.addr = argv.next().ok_or("--addr needs a value")?,
+            "--timeout" => {
+                let secs = argv.next().ok_or("--timeout needs a value")?;
+                let secs: f64 = secs
+                    .parse()
+                    .map_err(|_| format!("invalid --timeout {secs}"))?;
+                args.timeout =
+                    Duration::try_from_secs_f64(secs).map_err(|err| format!("--timeout: {err}"))?;
+            }
+            "--strict" => args.strict = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            other => return Err(format!("unknown argument {other}\n{USAGE}")),
+        }
+    }
+    Ok(args)
+}
+
+/// Returns the HTTP status code of `GET /node/health`.
+fn probe(args: &Args) -> Result<u16, String> {
+    let addr = args
+        .addr
+        .to_socket_addrs()
+        .map_err(|err| format!("{}: {err}", args.addr))?
+        .next()
+        .ok_or_else(|| format!("{} did not resolve", args.addr))?;
+    let mut stream = TcpStream::connect_timeout(&addr, args.timeout)
+        .map_err(|err| format!("connecting to {addr}: {err}"))?;
+    stream
+        .set_read_timeout(Some(args.timeout))
+        .and_then(|_| stream.set_write_timeout(Some(args.timeout)))
+        .map_err(|err| err.to_string())?;
+
+    write!(
+        stream,
+        "GET /node/health HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        args.addr
+    )
+    .map_err(|err| format!("sending request: {err}"))?;
+
+    // The status line is all we need; it arrives in the first read.
+    let mut buf = [0u8; 64];
+    let n = stream
+        .read(&mut buf)
+        .map_err(|err| format!("reading response: {err}"))?;
+    let response = String::from_utf8_lossy(&buf[..n]);
+    response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| format!("malformed response: {response:?}"))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match probe(&args) {
+        Ok(200) => {
+            println!("Healthy");
+            ExitCode::SUCCESS
+        }
+        Ok(206) if !args.strict => {
+            println!("PartiallyHealthy");
+            ExitCode::SUCCESS
+        }
+        Ok(206) => {
+            println!("PartiallyHealthy");
+            ExitCode::FAILURE
+        }
+        Ok(503) => {
+            println!("Unhealthy");
+            ExitCode::FAILURE
+        }
+        Ok(status) => {
+            eprintln!("unexpected status {status} from /node/health");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! A typed client for node API servers.
+
+use crate::{NodeHealth, NodeService, ServiceStatus};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("node API request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("{path} returned unexpected status {status}")]
+    UnexpectedStatus { path: String, status: StatusCode },
+}
+
+errors::error_codes!(ClientError {
+    Http => ("nodeapi.unreachable", Network),
+    UnexpectedStatus => ("nodeapi.unexpected_status", Network),
+});
+
+/// The `/node` response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeInfo {
+    pub node_name: String,
+    pub spec_version: String,
+    pub node_version: String,
+}
+
+#[derive(Clone)]
+pub struct NodeApiClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl NodeApiClient {
+    /// `base_url` is the server root, e.g. `http://localhost:9010`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn node_info(&self) -> Result<NodeInfo, ClientError> {
+        Ok(self.get_ok("/node").await?.json().await?)
+    }
+
+    pub async fn health(&self) -> Result<NodeHealth, ClientError> {
+        let path = "/node/health";
+        match self.get(path).await?.status() {
+            StatusCode::OK => Ok(NodeHealth::Healthy),
+            StatusCode::PARTIAL_CONTENT => Ok(NodeHealth::PartiallyHealthy),
+            StatusCode::SERVICE_UNAVAILABLE => Ok(NodeHealth::Unhealthy),
+            status => Err(unexpected(path, status)),
+        }
+    }
+
+    pub async fn services(&self) -> Result<Vec<NodeService>, ClientError> {
+        #[derive(Deserialize)]
+        struct Services {
+            services: Vec<NodeService>,
+        }
+        let response: Services = self.get_ok("/node/services").await?.json().await?;
+        Ok(response.services)
+    }
+
+    /// `None` if the node doesn't know the service.
+    pub async fn service_health(&self, id: &str) -> Result<Option<ServiceStatus>, ClientError> {
+        let path = format!("/node/services/{}/health", encode_segment(id));
+        match self.get(&path).await?.status() {
+            StatusCode::OK => Ok(Some(ServiceStatus::Up)),
+            StatusCode::PARTIAL_CONTENT => Ok(Some(ServiceStatus::Initializing)),
+            StatusCode::SERVICE_UNAVAILABLE => Ok(Some(ServiceStatus::Down)),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Err(unexpected(&path, status)),
+        }
+    }
+
+    pub(crate) async fn get(&self, path: &str) -> Result<reqwest::Response, ClientError> {
+        Ok(self
+            .http
+            .get(format!("{}{path}", self.base_url))
+            .send()
+            .await?)
+    }
+
+    async fn get_ok(&self, path: &str) -> Result<reqwest::Response, ClientError> {
+        let response = self.get(path).await?;
+        match response.status() {
+            StatusCode::OK => Ok(response),
+            status => Err(unexpected(path, status)),
+        }
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters, so ids
+/// containing `/`, `?` or `#` stay a single path segment.
+pub(crate) fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn unexpected(path: &str, status: StatusCode) -> ClientError {
+    ClientError::UnexpectedStatus {
+        path: path.to_string(),
+        status,
+    }
+}
@@ -0,0 +1,337 @@
+//! The node API over gRPC, for infrastructure that probes over gRPC instead
+//! of HTTP. Both services read the same state as the HTTP router.
+//!
+//! `nodeapi.v1.NodeApi` mirrors the HTTP routes, with messages hand-written
+//! in [`pb`] so building doesn't need `protoc`:
+//!
+//! ```proto
+//! service NodeApi {
+//!   rpc GetNodeInfo(GetNodeInfoRequest) returns (NodeInfo);
+//!   rpc GetHealth(GetHealthRequest) returns (GetHealthResponse);
+//!   rpc ListServices(ListServicesRequest) returns (ListServicesResponse);
+//!   // NOT_FOUND if the service isn't registered.
+//!   rpc GetServiceHealth(GetServiceHealthRequest) returns (GetServiceHealthResponse);
+//! }
+//! ```
+//!
+//! `grpc.health.v1.Health` reports the whole node under the empty service
+//! name, and each registered service under its id. `PartiallyHealthy` nodes
+//! still report `SERVING`, as their `/node/health` 206 is a success too.
+
+use crate::{NodeApi, NodeHealth, ServiceStatus};
+use std::collections::HashSet;
+use std::future::{ready, Ready};
+use std::net::SocketAddr;
+use tokio::task::JoinHandle;
+use tonic::codegen::{http, Body, BoxFuture, Context, Poll, Service, StdError};
+use tonic::server::{Grpc, NamedService, UnaryService};
+use tonic::Status;
+use tonic_health::pb::health_server::{Health, HealthServer};
+use tonic_health::pb::HealthCheckRequest;
+use tonic_health::server::{HealthReporter, HealthService};
+use tonic_health::ServingStatus;
+use tonic_prost::ProstCodec;
+
+/// `nodeapi.v1` messages.
+pub mod pb {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetNodeInfoRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NodeInfo {
+        #[prost(string, tag = "1")]
+        pub node_name: String,
+        #[prost(string, tag = "2")]
+        pub spec_version: String,
+        #[prost(string, tag = "3")]
+        pub node_version: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetHealthRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetHealthResponse {
+        #[prost(enumeration = "NodeHealth", tag = "1")]
+        pub health: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListServicesRequest {}
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListServicesResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub services: Vec<Service>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Service {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(string, tag = "2")]
+        pub name: String,
+        #[prost(string, tag = "3")]
+        pub description: String,
+        #[prost(enumeration = "ServiceStatus", tag = "4")]
+        pub status: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetServiceHealthRequest {
+        #[prost(string, tag = "1")]
+        pub service_id: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetServiceHealthResponse {
+        #[prost(enumeration = "ServiceStatus", tag = "1")]
+        pub status: i32,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum NodeHealth {
+        Unspecified = 0,
+        Healthy = 1,
+        PartiallyHealthy = 2,
+        Unhealthy = 3,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum ServiceStatus {
+        Unspecified = 0,
+        Up = 1,
+        Down = 2,
+        Initializing = 3,
+    }
+}
+
+impl From<&NodeHealth> for pb::NodeHealth {
+    fn from(health: &NodeHealth) -> Self {
+        match health {
+            NodeHealth::Healthy => Self::Healthy,
+            NodeHealth::PartiallyHealthy => Self::PartiallyHealthy,
+            NodeHealth::Unhealthy => Self::Unhealthy,
+        }
+    }
+}
+
+impl From<&ServiceStatus> for pb::ServiceStatus {
+    fn from(status: &ServiceStatus) -> Self {
+        match status {
+            ServiceStatus::Up => Self::Up,
+            ServiceStatus::Down => Self::Down,
+            ServiceStatus::Initializing => Self::Initializing,
+        }
+    }
+}
+
+pub const SERVICE_NAME: &str = "nodeapi.v1.NodeApi";
+
+/// Serves `nodeapi.v1.NodeApi` from a [`NodeApi`].
+#[derive(Clone)]
+pub struct NodeApiServer {
+    api: NodeApi,
+}
+
+impl NodeApiServer {
+    fn node_info(api: &NodeApi, _: pb::GetNodeInfoRequest) -> Result<pb::NodeInfo, Status> {
+        Ok(pb::NodeInfo {
+            node_name: api.avs_node_name.clone(),
+            spec_version: crate::SPEC_VERSION.to_string(),
+            node_version: api.avs_node_sem_ver.clone(),
+        })
+    }
+
+    fn health(api: &NodeApi, _: pb::GetHealthRequest) -> Result<pb::GetHealthResponse, Status> {
+        Ok(pb::GetHealthResponse {
+            health: pb::NodeHealth::from(&api.health()).into(),
+        })
+    }
+
+    fn services(
+        api: &NodeApi,
+        _: pb::ListServicesRequest,
+    ) -> Result<pb::ListServicesResponse, Status> {
+        let services = api
+            .services()
+            .into_iter()
+            .map(|service| pb::Service {
+                status: pb::ServiceStatus::from(&service.status).into(),
+                id: service.id,
+                name: service.name,
+                description: service.description,
+            })
+            .collect();
+        Ok(pb::ListServicesResponse { services })
+    }
+
+    fn service_health(
+        api: &NodeApi,
+        request: pb::GetServiceHealthRequest,
+    ) -> Result<pb::GetServiceHealthResponse, Status> {
+        match api.services().iter().find(|s| s.id == request.service_id) {
+            Some(service) => Ok(pb::GetServiceHealthResponse {
+                status: pb::ServiceStatus::from(&service.status).into(),
+            }),
+            None => Err(Status::not_found(format!(
+                "service {} not found",
+                request.service_id
+            ))),
+        }
+    }
+}
+
+impl<B> Service<http::Request<B>> for NodeApiServer
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let api = self.api.clone();
+        match request.uri().path() {
+            "/nodeapi.v1.NodeApi/GetNodeInfo" => unary(api, Self::node_info, request),
+            "/nodeapi.v1.NodeApi/GetHealth" => unary(api, Self::health, request),
+            "/nodeapi.v1.NodeApi/ListServices" => unary(api, Self::services, request),
+            "/nodeapi.v1.NodeApi/GetServiceHealth" => unary(api, Self::service_health, request),
+            _ => Box::pin(async { Ok(Status::unimplemented("unknown method").into_http()) }),
+        }
+    }
+}
+
+impl NamedService for NodeApiServer {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+type Handler<Req, Resp> = fn(&NodeApi, Req) -> Result<Resp, Status>;
+
+struct Unary<Req, Resp> {
+    api: NodeApi,
+    handler: Handler<Req, Resp>,
+}
+
+impl<Req, Resp> UnaryService<Req> for Unary<Req, Resp> {
+    type Response = Resp;
+    type Future = Ready<Result<tonic::Response<Resp>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+        ready((self.handler)(&self.api, request.into_inner()).map(tonic::Response::new))
+    }
+}
+
+fn unary<Req, Resp, B>(
+    api: NodeApi,
+    handler: Handler<Req, Resp>,
+    request: http::Request<B>,
+) -> BoxFuture<http::Response<tonic::body::Body>, std::convert::Infallible>
+where
+    Req: prost::Message + Default + Send + 'static,
+    Resp: prost::Message + Send + 'static,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    Box::pin(async move {
+        let mut grpc = Grpc::new(ProstCodec::<Resp, Req>::default());
+        Ok(grpc.unary(Unary { api, handler }, request).await)
+    })
+}
+
+/// A [`HealthService`] that stops its sync task once the last clone of the
+/// server serving it is dropped.
+pub struct SyncedHealth {
+    service: HealthService,
+    sync: JoinHandle<()>,
+}
+
+impl Drop for SyncedHealth {
+    fn drop(&mut self) {
+        self.sync.abort();
+    }
+}
+
+#[tonic::async_trait]
+impl Health for SyncedHealth {
+    type WatchStream = <HealthService as Health>::WatchStream;
+
+    async fn check(
+        &self,
+        request: tonic::Request<HealthCheckRequest>,
+    ) -> Result<tonic::Response<tonic_health::pb::HealthCheckResponse>, Status> {
+        self.service.check(request).await
+    }
+
+    async fn watch(
+        &self,
+        request: tonic::Request<HealthCheckRequest>,
+    ) -> Result<tonic::Response<Self::WatchStream>, Status> {
+        self.service.watch(request).await
+    }
+}
+
+impl NodeApi {
+    pub fn grpc_service(&self) -> NodeApiServer {
+        NodeApiServer { api: self.clone() }
+    }
+
+    /// A health service kept in sync with this node's state by a background
+    /// task, which runs until the server is dropped. Must be called inside a
+    /// tokio runtime.
+    pub fn grpc_health_service(&self) -> HealthServer<SyncedHealth> {
+        let reporter = HealthReporter::new();
+        let service = HealthService::from_health_reporter(reporter.clone());
+        let sync = tokio::spawn(sync(self.clone(), reporter));
+        HealthServer::new(SyncedHealth { service, sync })
+    }
+
+    /// Serves [`NodeApi::grpc_service`] and [`NodeApi::grpc_health_service`]
+    /// until CTRL+C.
+    pub async fn start_grpc(&self, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+        self.logger
+            .info("node API gRPC listening", &[("addr", &addr)]);
+        tonic::transport::Server::builder()
+            .add_service(self.grpc_service())
+            .add_service(self.grpc_health_service())
+            .serve_with_shutdown(addr, crate::shutdown_signal())
+            .await
+    }
+}
+
+async fn sync(api: NodeApi, mut reporter: HealthReporter) {
+    let mut changes = api.subscribe();
+    let mut reported = HashSet::new();
+    loop {
+        let node = match api.health() {
+            NodeHealth::Healthy | NodeHealth::PartiallyHealthy => ServingStatus::Serving,
+            NodeHealth::Unhealthy => ServingStatus::NotServing,
+        };
+        reporter.set_service_status("", node).await;
+
+        let services = api.services();
+        for service in &services {
+            let status = match service.status {
+                ServiceStatus::Up => ServingStatus::Serving,
+                ServiceStatus::Down | ServiceStatus::Initializing => ServingStatus::NotServing,
+            };
+            reporter.set_service_status(&service.id, status).await;
+        }
+        let current: HashSet<_> = services.into_iter().map(|s| s.id).collect();
+        for gone in reported.difference(&current) {
+            reporter.clear_service_status(gone).await;
+        }
+        reported = current;
+
+        if changes.changed().await.is_err() {
+            return;
+        }
+    }
+}
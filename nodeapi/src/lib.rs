@@ -0,0 +1,229 @@
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+use axum::extract::Path;
+use axum::Extension;
+use axum::{http::StatusCode, routing::get, Json, Router};
+use logging::{SharedLogger, TracingLogger};
+use metrics::http::HttpMetrics;
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::signal;
+use tokio::sync::watch;
+
+/// The node API spec version served on `/node`.
+pub const SPEC_VERSION: &str = "v0.0.1";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NodeHealth {
+    Healthy,
+    PartiallyHealthy,
+    Unhealthy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServiceStatus {
+    Up,
+    Down,
+    Initializing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeService {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub status: ServiceStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NodeApiError {
+    #[error("service {0} not found")]
+    ServiceNotFound(String),
+}
+
+errors::error_codes!(NodeApiError {
+    ServiceNotFound => ("nodeapi.service_not_found", Validation),
+});
+
+#[derive(Clone)]
+pub struct NodeApi {
+    avs_node_name: String,
+    avs_node_sem_ver: String,
+    health: Arc<Mutex<NodeHealth>>,
+    node_services: Arc<Mutex<Vec<NodeService>>>,
+    metrics: Option<(Registry, HttpMetrics)>,
+    logger: SharedLogger,
+    changes: Arc<watch::Sender<()>>,
+}
+
+impl NodeApi {
+    pub fn new(avs_node_name: impl Into<String>, avs_node_sem_ver: impl Into<String>) -> Self {
+        Self {
+            avs_node_name: avs_node_name.into(),
+            avs_node_sem_ver: avs_node_sem_ver.into(),
+            health: Arc::new(Mutex::new(NodeHealth::Healthy)),
+            node_services: Arc::new(Mutex::new(vec![])),
+            metrics: None,
+            logger: Arc::new(TracingLogger),
+            changes: Arc::new(watch::channel(()).0),
+        }
+    }
+
+    /// Also serves `registry` on `/metrics` from the node API server, and
+    /// records per-route request metrics into it.
+    pub fn with_metrics(mut self, registry: Registry) -> prometheus::Result<Self> {
+        let http_metrics = HttpMetrics::new(&self.avs_node_name, &registry)?;
+        self.metrics = Some((registry, http_metrics));
+        Ok(self)
+    }
+
+    /// Defaults to [`TracingLogger`].
+    pub fn with_logger(mut self, logger: SharedLogger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    pub fn health(&self) -> NodeHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    pub fn update_health(&self, health: NodeHealth) {
+        *self.health.lock().unwrap() = health;
+        self.changes.send_replace(());
+    }
+
+    pub fn services(&self) -> Vec<NodeService> {
+        self.node_services.lock().unwrap().clone()
+    }
+
+    pub fn register_new_service(
+        &self,
+        id: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        status: ServiceStatus,
+    ) {
+        self.node_services.lock().unwrap().push(NodeService {
+            id: id.into(),
+            name: name.into(),
+            description: description.into(),
+            status,
+        });
+        self.changes.send_replace(());
+    }
+
+    pub fn update_service_status(
+        &self,
+        service_id: &str,
+        status: ServiceStatus,
+    ) -> Result<(), NodeApiError> {
+        let mut services = self.node_services.lock().unwrap();
+        match services.iter_mut().find(|s| s.id == service_id) {
+            Some(service) => {
+                service.status = status;
+                self.changes.send_replace(());
+                Ok(())
+            }
+            None => Err(NodeApiError::ServiceNotFound(service_id.to_string())),
+        }
+    }
+
+    pub fn deregister_service(&self, service_id: &str) -> Result<(), NodeApiError> {
+        let mut services = self.node_services.lock().unwrap();
+        match services.iter().position(|s| s.id == service_id) {
+            Some(index) => {
+                services.remove(index);
+                self.changes.send_replace(());
+                Ok(())
+            }
+            None => Err(NodeApiError::ServiceNotFound(service_id.to_string())),
+        }
+    }
+
+    /// Notified whenever the node health or a service changes.
+    pub fn subscribe(&self) -> watch::Receiver<()> {
+        self.changes.subscribe()
+    }
+
+    pub fn router(&self) -> Router {
+        let router = Router::new()
+            .route("/node", get(NodeApi::node_handler))
+            .route("/node/health", get(NodeApi::health_handler))
+            .route("/node/services", get(NodeApi::services_handler))
+            .route(
+                "/node/services/:service_id/health",
+                get(NodeApi::service_health_handler),
+            )
+            .layer(axum::Extension(Arc::new(self.clone())));
+
+        match &self.metrics {
+            Some((registry, http_metrics)) => {
+                http_metrics.instrument(router.merge(metrics::metrics_router(registry.clone())))
+            }
+            None => router,
+        }
+    }
+
+    pub async fn start(&self, addr: SocketAddr) -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        self.logger
+            .info("node API listening", &[("addr", &listener.local_addr()?)]);
+
+        axum::serve(listener, self.router())
+            .with_graceful_shutdown(shutdown_signal())
+            .await
+    }
+
+    async fn node_handler(Extension(api): Extension<Arc<NodeApi>>) -> Json<serde_json::Value> {
+        Json(json!({
+            "node_name": api.avs_node_name,
+            "spec_version": SPEC_VERSION,
+            "node_version": api.avs_node_sem_ver,
+        }))
+    }
+
+    async fn health_handler(Extension(api): Extension<Arc<NodeApi>>) -> StatusCode {
+        let health = api.health.lock().unwrap();
+        match *health {
+            NodeHealth::Healthy => StatusCode::OK,
+            NodeHealth::PartiallyHealthy => StatusCode::PARTIAL_CONTENT,
+            NodeHealth::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    async fn services_handler(Extension(api): Extension<Arc<NodeApi>>) -> Json<serde_json::Value> {
+        let services = api.node_services.lock().unwrap();
+        Json(json!({ "services": *services }))
+    }
+
+    async fn service_health_handler(
+        Extension(api): Extension<Arc<NodeApi>>,
+        Path(service_id): Path<String>,
+    ) -> StatusCode {
+        let services = api.node_services.lock().unwrap();
+        let service = services.iter().find(|s| s.id == service_id);
+
+        match service {
+            Some(s) => match s.status {
+                ServiceStatus::Up => StatusCode::OK,
+                ServiceStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+                ServiceStatus::Initializing => StatusCode::PARTIAL_CONTENT,
+            },
+            None => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
+async fn shutdown_signal() {
+    signal::ctrl_c()
+        .await
+        .expect("failed to install CTRL+C signal handler");
+}
@@ -1,105 +1,46 @@
-use axum::extract::Path;
-use axum::Extension;
-use axum::{http::StatusCode, routing::get, Json, Router};
+use config::ConfigLoader;
+use errors::EigenError;
+use logging::{LogConfig, LogFormat};
+use nodeapi::NodeApi;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use std::net::SocketAddr;
-use std::sync::{Arc, Mutex};
-use tokio::signal;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-enum NodeHealth {
-    Healthy,
-    PartiallyHealthy,
-    Unhealthy,
+#[derive(Serialize, Deserialize)]
+struct Settings {
+    node_name: String,
+    node_version: String,
+    addr: SocketAddr,
+    log_format: LogFormat,
+    /// Overridden by `RUST_LOG`.
+    log_filter: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-enum ServiceStatus {
-    Up,
-    Down,
-    Initializing,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct NodeService {
-    id: String,
-    name: String,
-    description: String,
-    status: ServiceStatus,
-}
-
-#[derive(Clone)]
-struct NodeApi {
-    avs_node_name: String,
-    avs_node_sem_ver: String,
-    health: Arc<Mutex<NodeHealth>>,
-    node_services: Arc<Mutex<Vec<NodeService>>>,
-}
-
-impl NodeApi {
-    async fn node_handler(Extension(api): Extension<Arc<NodeApi>>) -> Json<serde_json::Value> {
-        Json(json!({
-            "node_name": api.avs_node_name,
-            "spec_version": "v0.0.1",
-            "node_version": api.avs_node_sem_ver,
-        }))
-    }
-
-    async fn health_handler(Extension(api): Extension<Arc<NodeApi>>) -> StatusCode {
-        let health = api.health.lock().unwrap();
-        match *health {
-            NodeHealth::Healthy => StatusCode::OK,
-            NodeHealth::PartiallyHealthy => StatusCode::PARTIAL_CONTENT,
-            NodeHealth::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
-        }
-    }
-
-    async fn service_health_handler(
-        Extension(api): Extension<Arc<NodeApi>>,
-        Path(service_id): Path<String>,
-    ) -> StatusCode {
-        let services = api.node_services.lock().unwrap();
-        let service = services.iter().find(|s| s.id == service_id);
-
-        match service {
-            Some(s) => match s.status {
-                ServiceStatus::Up => StatusCode::OK,
-                ServiceStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
-                ServiceStatus::Initializing => StatusCode::PARTIAL_CONTENT,
-            },
-            None => StatusCode::NOT_FOUND,
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            node_name: "NodeName".to_string(),
+            node_version: "v0.0.1".to_string(),
+            addr: SocketAddr::from(([127, 0, 0, 1], 3000)),
+            log_format: LogFormat::Pretty,
+            log_filter: "info".to_string(),
         }
     }
 }
 
 #[tokio::main]
-async fn main() {
-    let api = Arc::new(NodeApi {
-        avs_node_name: "NodeName".to_string(),
-        avs_node_sem_ver: "v0.0.1".to_string(),
-        health: Arc::new(Mutex::new(NodeHealth::Healthy)),
-        node_services: Arc::new(Mutex::new(vec![])),
-    });
-
-    let app = Router::new()
-        .route("/node", get(NodeApi::node_handler))
-        .route("/node/health", get(NodeApi::health_handler))
-        .route(
-            "/node/services/:service_id/health",
-            get(NodeApi::service_health_handler),
-        )
-        .layer(axum::Extension(api));
-
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    println!("Listening on {}", addr);
-
-    axum::serve(listener, app).await.unwrap();
-}
-
-async fn shutdown_signal() {
-    let _ = signal::ctrl_c()
-        .await
-        .expect("failed to install CTRL+C signal handler");
+async fn main() -> Result<(), EigenError> {
+    // e.g. EIGEN_NODEAPI_ADDR=0.0.0.0:9010 EIGEN_NODEAPI_LOG_FORMAT=json
+    let settings: Settings = ConfigLoader::new()
+        .with_defaults(&Settings::default())?
+        .with_env("EIGEN_NODEAPI")
+        .load()?
+        .extract()?;
+    logging::init_logging(LogConfig {
+        format: settings.log_format,
+        filter: settings.log_filter,
+    })?;
+
+    let api = NodeApi::new(settings.node_name, settings.node_version);
+    api.start(settings.addr).await?;
+    Ok(())
 }
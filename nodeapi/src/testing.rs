@@ -0,0 +1,173 @@
+//! Runs a [`NodeApi`] on an ephemeral port and checks it against the node API
+//! spec, so embedders can verify their health wiring in CI:
+//!
+//! ```no_run
+//! # async fn example() {
+//! let server = nodeapi::testing::TestServer::spawn(nodeapi::NodeApi::new("my-avs", "v1.0.0"))
+//!     .await
+//!     .unwrap();
+//! server.assert_spec_compliant().await;
+//! # }
+//! ```
+
+use crate::client::{encode_segment, NodeApiClient};
+use crate::{NodeApi, NodeHealth, ServiceStatus};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::net::SocketAddr;
+use tokio::task::JoinHandle;
+
+/// Stops the server when dropped.
+pub struct TestServer {
+    api: NodeApi,
+    addr: SocketAddr,
+    client: NodeApiClient,
+    server: JoinHandle<()>,
+}
+
+impl TestServer {
+    pub async fn spawn(api: NodeApi) -> std::io::Result<Self> {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let router = api.router();
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+        Ok(Self {
+            api,
+            addr,
+            client: NodeApiClient::new(format!("http://{addr}")),
+            server,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn client(&self) -> &NodeApiClient {
+        &self.client
+    }
+
+    /// Spec violations found by querying every route, compared against the
+    /// state of the served [`NodeApi`].
+    pub async fn check_spec(&self) -> Vec<String> {
+        let mut violations = vec![];
+
+        match self.get_json("/node").await {
+            Ok(node) => {
+                for field in ["node_name", "spec_version", "node_version"] {
+                    if !node[field].is_string() {
+                        violations.push(format!("/node: {field} must be a string"));
+                    }
+                }
+            }
+            Err(err) => violations.push(err),
+        }
+
+        let expected = match self.api.health() {
+            NodeHealth::Healthy => StatusCode::OK,
+            NodeHealth::PartiallyHealthy => StatusCode::PARTIAL_CONTENT,
+            NodeHealth::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        self.check_status("/node/health", expected, &mut violations)
+            .await;
+
+        match self.get_json("/node/services").await {
+            Ok(body) => match body["services"].as_array() {
+                Some(services) => {
+                    for service in services {
+                        let id = service["id"].as_str().unwrap_or("<missing id>");
+                        for field in ["id", "name", "description"] {
+                            if !service[field].is_string() {
+                                violations.push(format!(
+                                    "/node/services: service {id}: {field} must be a string"
+                                ));
+                            }
+                        }
+                        if !matches!(
+                            service["status"].as_str(),
+                            Some("Up" | "Down" | "Initializing")
+                        ) {
+                            violations.push(format!(
+                                "/node/services: service {id}: status must be Up, Down or Initializing"
+                            ));
+                        }
+                    }
+                    if services.len() != self.api.services().len() {
+                        violations.push(format!(
+                            "/node/services: lists {} services, node has {}",
+                            services.len(),
+                            self.api.services().len()
+                        ));
+                    }
+                }
+                None => violations.push("/node/services: services must be an array".to_string()),
+            },
+            Err(err) => violations.push(err),
+        }
+
+        for service in self.api.services() {
+            let expected = match service.status {
+                ServiceStatus::Up => StatusCode::OK,
+                ServiceStatus::Initializing => StatusCode::PARTIAL_CONTENT,
+                ServiceStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+            };
+            let path = format!("/node/services/{}/health", encode_segment(&service.id));
+            self.check_status(&path, expected, &mut violations).await;
+        }
+        self.check_status(
+            "/node/services/__unregistered__/health",
+            StatusCode::NOT_FOUND,
+            &mut violations,
+        )
+        .await;
+
+        violations
+    }
+
+    /// Panics listing every spec violation.
+    pub async fn assert_spec_compliant(&self) {
+        let violations = self.check_spec().await;
+        if !violations.is_empty() {
+            let list: Vec<_> = violations.iter().map(|v| format!("  {v}")).collect();
+            panic!(
+                "{} node API spec violation(s):\n{}",
+                violations.len(),
+                list.join("\n")
+            );
+        }
+    }
+
+    async fn get_json(&self, path: &str) -> Result<Value, String> {
+        let response = self
+            .client
+            .get(path)
+            .await
+            .map_err(|err| format!("{path}: {err}"))?;
+        if response.status() != StatusCode::OK {
+            return Err(format!("{path}: expected 200, got {}", response.status()));
+        }
+        response
+            .json()
+            .await
+            .map_err(|err| format!("{path}: invalid JSON: {err}"))
+    }
+
+    async fn check_status(&self, path: &str, expected: StatusCode, violations: &mut Vec<String>) {
+        match self.client.get(path).await {
+            Ok(response) if response.status() == expected => {}
+            Ok(response) => violations.push(format!(
+                "{path}: expected {expected}, got {}",
+                response.status()
+            )),
+            Err(err) => violations.push(format!("{path}: {err}")),
+        }
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
@@ -0,0 +1,156 @@
+use nodeapi::grpc::pb;
+use nodeapi::{NodeApi, NodeHealth, ServiceStatus};
+use std::time::Duration;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::Channel;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_health::pb::{health_check_response, HealthCheckRequest};
+use tonic_prost::ProstCodec;
+
+async fn serve(api: &NodeApi) -> Channel {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = tonic::transport::Server::builder()
+        .add_service(api.grpc_service())
+        .add_service(api.grpc_health_service());
+    tokio::spawn(router.serve_with_incoming(TcpIncoming::from(listener)));
+    Channel::from_shared(format!("http://{addr}"))
+        .unwrap()
+        .connect()
+        .await
+        .unwrap()
+}
+
+async fn call<Req, Resp>(
+    channel: &Channel,
+    method: &str,
+    request: Req,
+) -> Result<Resp, tonic::Status>
+where
+    Req: prost::Message + Send + Sync + 'static,
+    Resp: prost::Message + Default + Send + Sync + 'static,
+{
+    let mut grpc = tonic::client::Grpc::new(channel.clone());
+    grpc.ready().await.unwrap();
+    let path = PathAndQuery::try_from(format!("/nodeapi.v1.NodeApi/{method}")).unwrap();
+    grpc.unary(tonic::Request::new(request), path, ProstCodec::default())
+        .await
+        .map(tonic::Response::into_inner)
+}
+
+fn api() -> NodeApi {
+    let api = NodeApi::new("test-avs", "v1.2.3");
+    api.register_new_service("up", "Up", "a running service", ServiceStatus::Up);
+    api.register_new_service("down", "Down", "a stopped service", ServiceStatus::Down);
+    api
+}
+
+#[tokio::test]
+async fn serves_node_info_and_services() {
+    let api = api();
+    let channel = serve(&api).await;
+
+    let info: pb::NodeInfo = call(&channel, "GetNodeInfo", pb::GetNodeInfoRequest {})
+        .await
+        .unwrap();
+    assert_eq!(info.node_name, "test-avs");
+    assert_eq!(info.spec_version, nodeapi::SPEC_VERSION);
+    assert_eq!(info.node_version, "v1.2.3");
+
+    api.update_health(NodeHealth::PartiallyHealthy);
+    let health: pb::GetHealthResponse = call(&channel, "GetHealth", pb::GetHealthRequest {})
+        .await
+        .unwrap();
+    assert_eq!(health.health(), pb::NodeHealth::PartiallyHealthy);
+
+    let services: pb::ListServicesResponse =
+        call(&channel, "ListServices", pb::ListServicesRequest {})
+            .await
+            .unwrap();
+    let statuses: Vec<_> = services
+        .services
+        .iter()
+        .map(|s| (s.id.as_str(), s.status()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("up", pb::ServiceStatus::Up),
+            ("down", pb::ServiceStatus::Down)
+        ]
+    );
+
+    let service_health = |id: &str| {
+        call::<_, pb::GetServiceHealthResponse>(
+            &channel,
+            "GetServiceHealth",
+            pb::GetServiceHealthRequest {
+                service_id: id.to_string(),
+            },
+        )
+    };
+    assert_eq!(
+        service_health("down").await.unwrap().status(),
+        pb::ServiceStatus::Down
+    );
+    assert_eq!(
+        service_health("missing").await.unwrap_err().code(),
+        tonic::Code::NotFound
+    );
+
+    let err = call::<_, pb::NodeInfo>(&channel, "Unknown", pb::GetNodeInfoRequest {})
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::Unimplemented);
+}
+
+#[tokio::test]
+async fn health_service_follows_node_state() {
+    use health_check_response::ServingStatus;
+
+    let api = api();
+    let mut health = HealthClient::new(serve(&api).await);
+    let mut check = async |service: &str| {
+        health
+            .check(HealthCheckRequest {
+                service: service.to_string(),
+            })
+            .await
+            .map(|response| response.into_inner().status())
+    };
+
+    assert_eq!(check("").await.unwrap(), ServingStatus::Serving);
+    assert_eq!(check("down").await.unwrap(), ServingStatus::NotServing);
+
+    api.update_service_status("down", ServiceStatus::Up)
+        .unwrap();
+    api.deregister_service("up").unwrap();
+    api.update_health(NodeHealth::Unhealthy);
+    // The sync task applies changes asynchronously.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(check("").await.unwrap(), ServingStatus::NotServing);
+    assert_eq!(check("down").await.unwrap(), ServingStatus::Serving);
+    assert_eq!(check("up").await.unwrap_err().code(), tonic::Code::NotFound);
+}
+
+#[tokio::test]
+async fn health_sync_task_ends_with_the_server() {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let api = api();
+    let before = metrics.num_alive_tasks();
+
+    let server = api.grpc_health_service();
+    let clone = server.clone();
+    assert_eq!(metrics.num_alive_tasks(), before + 1);
+    drop(server);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(metrics.num_alive_tasks(), before + 1);
+
+    drop(clone);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(metrics.num_alive_tasks(), before);
+}
@@ -0,0 +1,60 @@
+use nodeapi::testing::TestServer;
+use nodeapi::{NodeApi, NodeHealth};
+use std::net::SocketAddr;
+use std::process::Command;
+
+/// Runs `nodeapi-healthcheck` against `addr`, returning its exit code and
+/// stdout.
+async fn healthcheck(addr: SocketAddr, extra: &[&str]) -> (i32, String) {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_nodeapi-healthcheck"));
+    command
+        .args(["--addr", &addr.to_string(), "--timeout", "1"])
+        .args(extra)
+        .env_remove("EIGEN_NODEAPI_ADDR");
+    // The probe blocks, so keep it off the runtime serving the node API.
+    let output = tokio::task::spawn_blocking(move || command.output().unwrap())
+        .await
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    (output.status.code().unwrap(), stdout.trim().to_string())
+}
+
+#[tokio::test]
+async fn exit_codes_follow_node_health() {
+    let api = NodeApi::new("test-avs", "v1.2.3");
+    let server = TestServer::spawn(api.clone()).await.unwrap();
+    let addr = server.addr();
+
+    for (health, strict, expected) in [
+        (NodeHealth::Healthy, false, (0, "Healthy")),
+        (NodeHealth::Healthy, true, (0, "Healthy")),
+        (NodeHealth::PartiallyHealthy, false, (0, "PartiallyHealthy")),
+        (NodeHealth::PartiallyHealthy, true, (1, "PartiallyHealthy")),
+        (NodeHealth::Unhealthy, false, (1, "Unhealthy")),
+    ] {
+        api.update_health(health.clone());
+        let extra: &[&str] = if strict { &["--strict"] } else { &[] };
+        let (code, stdout) = healthcheck(addr, extra).await;
+        assert_eq!(
+            (code, stdout.as_str()),
+            expected,
+            "{health:?} strict={strict}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn unreachable_nodes_fail() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let (code, stdout) = healthcheck(addr, &[]).await;
+    assert_eq!((code, stdout.as_str()), (1, ""));
+}
+
+#[tokio::test]
+async fn invalid_arguments_fail() {
+    let (code, _) = healthcheck("127.0.0.1:1".parse().unwrap(), &["--bogus"]).await;
+    assert_eq!(code, 1);
+}
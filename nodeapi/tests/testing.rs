@@ -0,0 +1,85 @@
+use errors::{EigenError, ErrorCategory};
+use nodeapi::testing::TestServer;
+use nodeapi::{NodeApi, NodeApiError, NodeHealth, ServiceStatus};
+
+fn api() -> NodeApi {
+    let api = NodeApi::new("test-avs", "v1.2.3");
+    api.register_new_service("up", "Up", "a running service", ServiceStatus::Up);
+    api.register_new_service("down", "Down", "a stopped service", ServiceStatus::Down);
+    api.register_new_service(
+        "initializing",
+        "Initializing",
+        "a starting service",
+        ServiceStatus::Initializing,
+    );
+    api.register_new_service(
+        "da/writer v2",
+        "DA writer",
+        "an id that needs escaping",
+        ServiceStatus::Up,
+    );
+    api
+}
+
+#[tokio::test]
+async fn served_api_is_spec_compliant() {
+    let api = api();
+    let server = TestServer::spawn(api.clone()).await.unwrap();
+    server.assert_spec_compliant().await;
+
+    for health in [
+        NodeHealth::PartiallyHealthy,
+        NodeHealth::Unhealthy,
+        NodeHealth::Healthy,
+    ] {
+        api.update_health(health);
+        server.assert_spec_compliant().await;
+    }
+
+    api.update_service_status("down", ServiceStatus::Up)
+        .unwrap();
+    api.deregister_service("initializing").unwrap();
+    server.assert_spec_compliant().await;
+}
+
+#[tokio::test]
+async fn client_reads_every_service_status() {
+    let server = TestServer::spawn(api()).await.unwrap();
+    let client = server.client();
+
+    let info = client.node_info().await.unwrap();
+    assert_eq!(info.node_name, "test-avs");
+    assert_eq!(info.node_version, "v1.2.3");
+    assert_eq!(client.health().await.unwrap(), NodeHealth::Healthy);
+    assert_eq!(client.services().await.unwrap().len(), 4);
+
+    for (id, status) in [
+        ("up", ServiceStatus::Up),
+        ("down", ServiceStatus::Down),
+        ("initializing", ServiceStatus::Initializing),
+        ("da/writer v2", ServiceStatus::Up),
+    ] {
+        assert_eq!(
+            client.service_health(id).await.unwrap(),
+            Some(status),
+            "{id}"
+        );
+    }
+    assert_eq!(client.service_health("da").await.unwrap(), None);
+    assert_eq!(client.service_health("missing?x=1").await.unwrap(), None);
+}
+
+#[test]
+fn unknown_services_carry_codes() {
+    let err = EigenError::from(
+        api()
+            .update_service_status("missing", ServiceStatus::Up)
+            .unwrap_err(),
+    );
+    assert_eq!(err.code(), "nodeapi.service_not_found");
+    assert_eq!(err.category(), ErrorCategory::Validation);
+    assert_eq!(
+        err.downcast_ref(),
+        Some(&NodeApiError::ServiceNotFound("missing".to_string()))
+    );
+}
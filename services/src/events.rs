@@ -0,0 +1,126 @@
+//! A typed broadcast bus that services publish node events to. Metrics, the
+//! supervisor's health rollup and third-party code subscribe from it instead
+//! of being wired to each publisher.
+//!
+//! Subscribers that fall behind skip events, so state that must not be lost
+//! isn't kept only on the bus: the supervisor writes service statuses to the
+//! node API directly and publishes `ServiceStatusChanged` as a notification.
+
+use logging::SharedLogger;
+use metrics::quorum::QuorumMetrics;
+use nodeapi::ServiceStatus;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use types::{OperatorId, QuorumNum, QuorumNums, TaskIndex};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    OperatorRegistered {
+        operator_id: OperatorId,
+        quorums: QuorumNums,
+    },
+    OperatorDeregistered {
+        operator_id: OperatorId,
+        quorums: QuorumNums,
+    },
+    /// `operator_id`'s stake in `quorum`, in the quorum's weight units.
+    StakeUpdated {
+        operator_id: OperatorId,
+        quorum: QuorumNum,
+        stake: f64,
+    },
+    TaskAggregated {
+        task_index: TaskIndex,
+    },
+    /// Keeps node health at most `PartiallyHealthy` until a matching
+    /// `RpcEndpointRecovered`.
+    RpcEndpointDegraded {
+        endpoint: String,
+        reason: String,
+    },
+    RpcEndpointRecovered {
+        endpoint: String,
+    },
+    /// Published by the [`Supervisor`](crate::supervisor::Supervisor).
+    ServiceStatusChanged {
+        id: String,
+        status: ServiceStatus,
+    },
+}
+
+/// Cheap to clone; every clone publishes to the same subscribers.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl EventBus {
+    /// Subscribers that fall more than `capacity` events behind skip the
+    /// oldest ones.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            sender: broadcast::channel(capacity).0,
+        }
+    }
+
+    /// Events published with no subscribers are dropped.
+    pub fn publish(&self, event: Event) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Receives every event published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+}
+
+/// Mirrors `operator_id`'s registration and stake events into `metrics` until
+/// the runtime shuts down. Events about other operators are ignored.
+pub fn spawn_metrics_subscriber(
+    bus: &EventBus,
+    operator_id: OperatorId,
+    metrics: QuorumMetrics,
+    logger: SharedLogger,
+) -> JoinHandle<()> {
+    let mut events = bus.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(Event::OperatorRegistered {
+                    operator_id: id,
+                    quorums,
+                }) if id == operator_id => {
+                    for quorum in quorums {
+                        metrics.set_registered(quorum, true);
+                    }
+                }
+                Ok(Event::OperatorDeregistered {
+                    operator_id: id,
+                    quorums,
+                }) if id == operator_id => {
+                    for quorum in quorums {
+                        metrics.set_registered(quorum, false);
+                    }
+                }
+                Ok(Event::StakeUpdated {
+                    operator_id: id,
+                    quorum,
+                    stake,
+                }) if id == operator_id => {
+                    metrics.set_registered_stake(quorum, stake);
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    logger.warn("metrics subscriber lagged", &[("skipped", &skipped)]);
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    })
+}
@@ -0,0 +1,152 @@
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Delay schedule between attempts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Backoff {
+    Fixed(Duration),
+    /// `initial * multiplier^n`, capped at `max`. `jitter` in `[0, 1]` randomly
+    /// shaves up to that fraction off each delay so retrying callers spread out.
+    Exponential {
+        initial: Duration,
+        max: Duration,
+        multiplier: f64,
+        jitter: f64,
+    },
+}
+
+impl Backoff {
+    /// Delay to wait after the `retry`-th failed attempt (zero-based).
+    pub fn delay(&self, retry: u32) -> Duration {
+        match self {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential {
+                initial,
+                max,
+                multiplier,
+                jitter,
+            } => {
+                let exp = i32::try_from(retry).unwrap_or(i32::MAX);
+                let delay = (initial.as_secs_f64() * multiplier.powi(exp)).min(max.as_secs_f64());
+                let jitter = jitter.clamp(0.0, 1.0);
+                let factor = if jitter > 0.0 {
+                    rand::thread_rng().gen_range(1.0 - jitter..=1.0)
+                } else {
+                    1.0
+                };
+                // Past `Duration::MAX` when `max` is effectively uncapped.
+                Duration::try_from_secs_f64((delay * factor).max(0.0)).unwrap_or(*max)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub backoff: Backoff,
+    /// Total attempts including the first one. `None` retries until the budget runs out.
+    pub max_attempts: Option<u32>,
+    /// Wall-clock budget across all attempts. A retry whose delay would overrun
+    /// the budget is not attempted.
+    pub budget: Option<Duration>,
+}
+
+impl RetryPolicy {
+    pub fn fixed(delay: Duration, max_attempts: u32) -> Self {
+        Self {
+            backoff: Backoff::Fixed(delay),
+            max_attempts: Some(max_attempts),
+            budget: None,
+        }
+    }
+
+    pub fn exponential(initial: Duration, max: Duration, max_attempts: u32) -> Self {
+        Self {
+            backoff: Backoff::Exponential {
+                initial,
+                max,
+                multiplier: 2.0,
+                jitter: 0.5,
+            },
+            max_attempts: Some(max_attempts),
+            budget: None,
+        }
+    }
+
+    /// Retries with `backoff` until `budget` is spent, without an attempt cap.
+    pub fn budgeted(backoff: Backoff, budget: Duration) -> Self {
+        Self {
+            backoff,
+            max_attempts: None,
+            budget: Some(budget),
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        if let Backoff::Exponential { jitter: j, .. } = &mut self.backoff {
+            *j = jitter;
+        }
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::exponential(Duration::from_millis(100), Duration::from_secs(10), 5)
+    }
+}
+
+/// Runs `op` until it succeeds or `policy` gives up, returning the last error.
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(policy, op, |_| true).await
+}
+
+/// Like [`retry`], but stops immediately on errors `is_retryable` rejects, so
+/// permanent failures (reverts, bad input) are not retried like transient ones.
+pub async fn retry_if<T, E, F, Fut, P>(
+    policy: &RetryPolicy,
+    mut op: F,
+    mut is_retryable: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    let start = Instant::now();
+    let mut attempts = 0;
+    loop {
+        let err = match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        attempts += 1;
+        if !is_retryable(&err) || policy.max_attempts.is_some_and(|max| attempts >= max) {
+            return Err(err);
+        }
+        let delay = policy.backoff.delay(attempts - 1);
+        if policy
+            .budget
+            .is_some_and(|budget| start.elapsed().saturating_add(delay) > budget)
+        {
+            return Err(err);
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
@@ -0,0 +1,472 @@
+use crate::events::{Event, EventBus};
+use crate::retry::Backoff;
+use logging::{SharedLogger, TracingLogger};
+use nodeapi::{NodeApi, NodeHealth, ServiceStatus};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::watch;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::{Duration, Instant};
+
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+pub type ServiceFuture = Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send>>;
+
+type RunFn = Arc<dyn Fn(ServiceContext) -> ServiceFuture + Send + Sync>;
+
+/// How long a service must stay `Up` before its restart count is reset, so
+/// `max_restarts` limits consecutive crashes rather than crashes over the
+/// process lifetime.
+pub const STABLE_RUN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartPolicy {
+    Never,
+    /// Restart only when the service returns an error.
+    OnFailure {
+        max_restarts: Option<u32>,
+        backoff: Backoff,
+    },
+    /// Restart whenever the service exits, including clean exits.
+    Always {
+        max_restarts: Option<u32>,
+        backoff: Backoff,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SupervisorError {
+    #[error("service {0} is registered more than once")]
+    DuplicateService(String),
+    #[error("service {service} depends on unknown service {dependency}")]
+    UnknownDependency { service: String, dependency: String },
+    #[error("dependency cycle involving service {0}")]
+    DependencyCycle(String),
+}
+
+errors::error_codes!(SupervisorError {
+    DuplicateService => ("services.duplicate_service", Validation),
+    UnknownDependency => ("services.unknown_dependency", Validation),
+    DependencyCycle => ("services.dependency_cycle", Validation),
+});
+
+/// Handed to a service on every (re)start.
+#[derive(Clone)]
+pub struct ServiceContext {
+    id: String,
+    ready: Arc<watch::Sender<bool>>,
+    events: EventBus,
+}
+
+impl ServiceContext {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The supervisor's event bus, to publish events to.
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Marks the service `Up` and lets services depending on it start.
+    pub fn ready(&self) {
+        self.ready.send_replace(true);
+    }
+}
+
+pub struct ServiceSpec {
+    id: String,
+    name: String,
+    description: String,
+    depends_on: Vec<String>,
+    restart: RestartPolicy,
+    run: RunFn,
+}
+
+impl ServiceSpec {
+    pub fn new<F, Fut>(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        run: F,
+    ) -> Self
+    where
+        F: Fn(ServiceContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), BoxError>> + Send + 'static,
+    {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: description.into(),
+            depends_on: vec![],
+            restart: RestartPolicy::Never,
+            run: Arc::new(move |ctx| Box::pin(run(ctx))),
+        }
+    }
+
+    pub fn depends_on(mut self, id: impl Into<String>) -> Self {
+        self.depends_on.push(id.into());
+        self
+    }
+
+    pub fn restart(mut self, policy: RestartPolicy) -> Self {
+        self.restart = policy;
+        self
+    }
+}
+
+/// Runs a set of services in dependency order, restarts them per their
+/// [`RestartPolicy`] and mirrors their status into the node API. Status
+/// changes are also published to an [`EventBus`] as notifications, and any
+/// endpoint the bus reports as degraded caps node health at
+/// `PartiallyHealthy` until it recovers.
+pub struct Supervisor {
+    node_api: NodeApi,
+    services: Vec<ServiceSpec>,
+    logger: SharedLogger,
+    events: EventBus,
+}
+
+impl Supervisor {
+    pub fn new(node_api: NodeApi) -> Self {
+        Self {
+            node_api,
+            services: vec![],
+            logger: Arc::new(TracingLogger),
+            events: EventBus::default(),
+        }
+    }
+
+    /// Shares `events` with other publishers and subscribers instead of
+    /// using a private bus.
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn event_bus(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Defaults to [`TracingLogger`].
+    pub fn with_logger(mut self, logger: SharedLogger) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    pub fn with_service(mut self, service: ServiceSpec) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    pub fn start(self) -> Result<SupervisorHandle, SupervisorError> {
+        let order = start_order(&self.services)?;
+
+        let statuses = Arc::new(Mutex::new(HashMap::new()));
+        let mut ready = HashMap::new();
+        for service in &self.services {
+            self.node_api.register_new_service(
+                &service.id,
+                &service.name,
+                &service.description,
+                ServiceStatus::Initializing,
+            );
+            statuses
+                .lock()
+                .unwrap()
+                .insert(service.id.clone(), ServiceStatus::Initializing);
+            ready.insert(service.id.clone(), Arc::new(watch::channel(false).0));
+        }
+        let reporter = StatusReporter {
+            node_api: self.node_api.clone(),
+            statuses,
+            degraded: Arc::default(),
+            events: self.events.clone(),
+        };
+        reporter.rollup();
+        let endpoints = tokio::spawn(track_endpoints(
+            self.events.subscribe(),
+            reporter.clone(),
+            self.logger.clone(),
+        ));
+
+        let (shutdown, _) = watch::channel(false);
+        let mut tasks = JoinSet::new();
+        let mut services: HashMap<_, _> = self
+            .services
+            .into_iter()
+            .map(|s| (s.id.clone(), s))
+            .collect();
+        for id in order {
+            let service = services.remove(&id).unwrap();
+            let deps = service
+                .depends_on
+                .iter()
+                .map(|dep| (dep.clone(), ready[dep].subscribe()))
+                .collect();
+            let ctx = ServiceContext {
+                id: id.clone(),
+                ready: ready[&id].clone(),
+                events: self.events.clone(),
+            };
+            tasks.spawn(supervise(
+                service,
+                ctx,
+                deps,
+                reporter.clone(),
+                self.logger.clone(),
+                shutdown.subscribe(),
+            ));
+        }
+
+        Ok(SupervisorHandle {
+            shutdown,
+            tasks,
+            endpoints,
+        })
+    }
+}
+
+/// Dropping the handle cancels the services without waiting for them.
+pub struct SupervisorHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: JoinSet<()>,
+    endpoints: JoinHandle<()>,
+}
+
+impl SupervisorHandle {
+    /// Waits until every service has stopped for good.
+    pub async fn wait(mut self) {
+        while self.tasks.join_next().await.is_some() {}
+    }
+
+    /// Cancels all running services and waits for them to stop.
+    pub async fn shutdown(self) {
+        self.shutdown.send_replace(true);
+        self.wait().await;
+    }
+}
+
+impl Drop for SupervisorHandle {
+    fn drop(&mut self) {
+        self.endpoints.abort();
+    }
+}
+
+/// Writes service statuses straight to the node API, so they can't be lost
+/// the way a lagging bus subscriber loses events, then notifies the bus.
+#[derive(Clone)]
+struct StatusReporter {
+    node_api: NodeApi,
+    statuses: Arc<Mutex<HashMap<String, ServiceStatus>>>,
+    /// Endpoints reported degraded and not yet recovered.
+    degraded: Arc<Mutex<HashSet<String>>>,
+    events: EventBus,
+}
+
+impl StatusReporter {
+    fn set(&self, id: &str, status: ServiceStatus) {
+        // Only fails if the embedder deregistered the service themselves.
+        let _ = self.node_api.update_service_status(id, status.clone());
+        self.statuses
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), status.clone());
+        self.rollup();
+        self.events.publish(Event::ServiceStatusChanged {
+            id: id.to_string(),
+            status,
+        });
+    }
+
+    fn set_degraded(&self, endpoint: String, degraded: bool) {
+        let mut endpoints = self.degraded.lock().unwrap();
+        let changed = match degraded {
+            true => endpoints.insert(endpoint),
+            false => endpoints.remove(&endpoint),
+        };
+        drop(endpoints);
+        if changed {
+            self.rollup();
+        }
+    }
+
+    fn rollup(&self) {
+        let statuses = self.statuses.lock().unwrap();
+        let degraded = !self.degraded.lock().unwrap().is_empty();
+        let health = if statuses.values().all(|s| *s == ServiceStatus::Up) {
+            match degraded {
+                true => NodeHealth::PartiallyHealthy,
+                false => NodeHealth::Healthy,
+            }
+        } else if statuses.values().all(|s| *s == ServiceStatus::Down) {
+            NodeHealth::Unhealthy
+        } else {
+            NodeHealth::PartiallyHealthy
+        };
+        self.node_api.update_health(health);
+    }
+}
+
+/// Feeds endpoint events from the bus into `reporter`'s health rollup.
+async fn track_endpoints(
+    mut events: broadcast::Receiver<Event>,
+    reporter: StatusReporter,
+    logger: SharedLogger,
+) {
+    loop {
+        match events.recv().await {
+            Ok(Event::RpcEndpointDegraded { endpoint, .. }) => {
+                reporter.set_degraded(endpoint, true);
+            }
+            Ok(Event::RpcEndpointRecovered { endpoint }) => {
+                reporter.set_degraded(endpoint, false);
+            }
+            Ok(_) => {}
+            // Skipped endpoint events leave health as of the last one seen.
+            Err(RecvError::Lagged(skipped)) => {
+                logger.warn(
+                    "health rollup lagged, endpoint health may be stale",
+                    &[("skipped", &skipped)],
+                );
+            }
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+async fn supervise(
+    service: ServiceSpec,
+    ctx: ServiceContext,
+    mut deps: Vec<(String, watch::Receiver<bool>)>,
+    reporter: StatusReporter,
+    logger: SharedLogger,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut ready = ctx.ready.subscribe();
+    let mut restarts = 0;
+    loop {
+        for (dep_id, dep) in &mut deps {
+            tokio::select! {
+                res = dep.wait_for(|ready| *ready) => if res.is_err() {
+                    // The dependency stopped for good and will never be ready.
+                    logger.error(
+                        "service dependency stopped",
+                        &[("service", &ctx.id), ("dependency", dep_id)],
+                    );
+                    reporter.set(&ctx.id, ServiceStatus::Down);
+                    return;
+                },
+                _ = shutdown.wait_for(|stop| *stop) => return,
+            }
+        }
+
+        reporter.set(&ctx.id, ServiceStatus::Initializing);
+        let run = (service.run)(ctx.clone());
+        tokio::pin!(run);
+        let mut up_since = None;
+        let result = loop {
+            tokio::select! {
+                result = &mut run => break Some(result),
+                _ = ready.wait_for(|ready| *ready), if up_since.is_none() => {
+                    up_since = Some(Instant::now());
+                    reporter.set(&ctx.id, ServiceStatus::Up);
+                }
+                _ = shutdown.wait_for(|stop| *stop) => break None,
+            }
+        };
+        ctx.ready.send_replace(false);
+        reporter.set(&ctx.id, ServiceStatus::Down);
+        let Some(result) = result else { return };
+        if up_since.is_some_and(|since| since.elapsed() >= STABLE_RUN) {
+            restarts = 0;
+        }
+        match &result {
+            Ok(()) => logger.info("service exited", &[("service", &ctx.id)]),
+            Err(err) => logger.error("service failed", &[("service", &ctx.id), ("error", err)]),
+        }
+
+        let (max_restarts, backoff) = match (&service.restart, &result) {
+            (RestartPolicy::Never, _) | (RestartPolicy::OnFailure { .. }, Ok(())) => return,
+            (
+                RestartPolicy::OnFailure {
+                    max_restarts,
+                    backoff,
+                }
+                | RestartPolicy::Always {
+                    max_restarts,
+                    backoff,
+                },
+                _,
+            ) => (max_restarts, backoff),
+        };
+        if max_restarts.is_some_and(|max| restarts >= max) {
+            logger.warn(
+                "service reached its restart limit",
+                &[("service", &ctx.id), ("restarts", &restarts)],
+            );
+            return;
+        }
+        let delay = backoff.delay(restarts);
+        logger.info(
+            "restarting service",
+            &[("service", &ctx.id), ("delay", &format_args!("{delay:?}"))],
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.wait_for(|stop| *stop) => return,
+        }
+        restarts += 1;
+    }
+}
+
+fn start_order(services: &[ServiceSpec]) -> Result<Vec<String>, SupervisorError> {
+    let mut by_id = HashMap::new();
+    for service in services {
+        if by_id.insert(service.id.as_str(), service).is_some() {
+            return Err(SupervisorError::DuplicateService(service.id.clone()));
+        }
+    }
+    for service in services {
+        if let Some(dep) = service
+            .depends_on
+            .iter()
+            .find(|d| !by_id.contains_key(d.as_str()))
+        {
+            return Err(SupervisorError::UnknownDependency {
+                service: service.id.clone(),
+                dependency: dep.clone(),
+            });
+        }
+    }
+
+    fn visit<'a>(
+        id: &'a str,
+        by_id: &HashMap<&'a str, &'a ServiceSpec>,
+        visiting: &mut HashSet<&'a str>,
+        order: &mut Vec<String>,
+    ) -> Result<(), SupervisorError> {
+        if order.iter().any(|o| o == id) {
+            return Ok(());
+        }
+        if !visiting.insert(id) {
+            return Err(SupervisorError::DependencyCycle(id.to_string()));
+        }
+        for dep in &by_id[id].depends_on {
+            visit(dep, by_id, visiting, order)?;
+        }
+        visiting.remove(id);
+        order.push(id.to_string());
+        Ok(())
+    }
+
+    let mut order = vec![];
+    for service in services {
+        visit(&service.id, &by_id, &mut HashSet::new(), &mut order)?;
+    }
+    Ok(order)
+}
@@ -0,0 +1,177 @@
+use logging::NoopLogger;
+use metrics::quorum::QuorumMetrics;
+use nodeapi::{NodeApi, NodeHealth, ServiceStatus};
+use prometheus::{Encoder, Registry, TextEncoder};
+use services::events::{spawn_metrics_subscriber, Event, EventBus};
+use services::supervisor::{ServiceSpec, Supervisor};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::TryRecvError;
+use types::{OperatorId, QuorumNum, QuorumNums};
+
+const LOCAL: OperatorId = OperatorId([1; 32]);
+const OTHER: OperatorId = OperatorId([2; 32]);
+
+/// Lets every task run until it blocks.
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+}
+
+/// The value of `name` for `quorum`, if it has been recorded.
+fn gauge(registry: &Registry, name: &str, quorum: u8) -> Option<f64> {
+    let mut text = vec![];
+    TextEncoder::new()
+        .encode(&registry.gather(), &mut text)
+        .unwrap();
+    let prefix = format!("eigen_{name}{{");
+    let label = format!("quorum_number=\"{quorum}\"");
+    String::from_utf8(text)
+        .unwrap()
+        .lines()
+        .find(|line| line.starts_with(&prefix) && line.contains(&label))
+        .map(|line| line.rsplit(' ').next().unwrap().parse().unwrap())
+}
+
+#[tokio::test(start_paused = true)]
+async fn statuses_survive_a_flooded_bus() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let bus = EventBus::new(4);
+    let mut notifications = bus.subscribe();
+    let handle = Supervisor::new(api.clone())
+        .with_logger(Arc::new(NoopLogger))
+        .with_event_bus(bus)
+        .with_service(ServiceSpec::new("flood", "flood", "", |ctx| async move {
+            ctx.ready();
+            for stake in 0..2_000 {
+                ctx.events().publish(Event::StakeUpdated {
+                    operator_id: LOCAL,
+                    quorum: QuorumNum(0),
+                    stake: stake as f64,
+                });
+            }
+            std::future::pending().await
+        }))
+        .with_service(ServiceSpec::new("crash", "crash", "", |ctx| async move {
+            ctx.ready();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Err("boom".into())
+        }))
+        .start()
+        .unwrap();
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let statuses: Vec<_> = api.services().into_iter().map(|s| s.status).collect();
+    assert_eq!(statuses, [ServiceStatus::Up, ServiceStatus::Down]);
+    assert_eq!(api.health(), NodeHealth::PartiallyHealthy);
+
+    // The slow subscriber lagged but still sees the latest notification.
+    let mut last = None;
+    loop {
+        match notifications.try_recv() {
+            Ok(Event::ServiceStatusChanged { id, status }) => last = Some((id, status)),
+            Ok(_) | Err(TryRecvError::Lagged(_)) => {}
+            Err(_) => break,
+        }
+    }
+    assert_eq!(last, Some(("crash".to_string(), ServiceStatus::Down)));
+
+    handle.shutdown().await;
+    assert_eq!(api.health(), NodeHealth::Unhealthy);
+}
+
+#[tokio::test(start_paused = true)]
+async fn degraded_endpoints_cap_node_health() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let bus = EventBus::default();
+    let handle = Supervisor::new(api.clone())
+        .with_logger(Arc::new(NoopLogger))
+        .with_event_bus(bus.clone())
+        .with_service(ServiceSpec::new("ok", "ok", "", |ctx| async move {
+            ctx.ready();
+            std::future::pending().await
+        }))
+        .start()
+        .unwrap();
+    settle().await;
+    assert_eq!(api.health(), NodeHealth::Healthy);
+
+    let degraded = |endpoint: &str| Event::RpcEndpointDegraded {
+        endpoint: endpoint.to_string(),
+        reason: "timeout".to_string(),
+    };
+    let recovered = |endpoint: &str| Event::RpcEndpointRecovered {
+        endpoint: endpoint.to_string(),
+    };
+    bus.publish(degraded("a"));
+    bus.publish(degraded("b"));
+    bus.publish(recovered("a"));
+    settle().await;
+    assert_eq!(api.health(), NodeHealth::PartiallyHealthy);
+
+    bus.publish(recovered("b"));
+    settle().await;
+    assert_eq!(api.health(), NodeHealth::Healthy);
+
+    bus.publish(degraded("a"));
+    settle().await;
+    handle.shutdown().await;
+    assert_eq!(api.health(), NodeHealth::Unhealthy);
+}
+
+#[tokio::test]
+async fn endpoint_tracking_ends_with_the_handle() {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let before = metrics.num_alive_tasks();
+    let handle = Supervisor::new(NodeApi::new("node", "v0.0.1"))
+        .with_logger(Arc::new(NoopLogger))
+        .start()
+        .unwrap();
+    assert_eq!(metrics.num_alive_tasks(), before + 1);
+    handle.wait().await;
+    settle().await;
+    assert_eq!(metrics.num_alive_tasks(), before);
+}
+
+#[tokio::test(start_paused = true)]
+async fn metrics_track_only_the_local_operator() {
+    let registry = Registry::new();
+    let metrics = QuorumMetrics::new("avs", Default::default(), &registry).unwrap();
+    let bus = EventBus::default();
+    spawn_metrics_subscriber(&bus, LOCAL, metrics, Arc::new(NoopLogger));
+
+    bus.publish(Event::OperatorRegistered {
+        operator_id: LOCAL,
+        quorums: QuorumNums(vec![QuorumNum(0), QuorumNum(1)]),
+    });
+    bus.publish(Event::StakeUpdated {
+        operator_id: LOCAL,
+        quorum: QuorumNum(0),
+        stake: 32.0,
+    });
+    bus.publish(Event::StakeUpdated {
+        operator_id: OTHER,
+        quorum: QuorumNum(0),
+        stake: 1.0,
+    });
+    bus.publish(Event::OperatorDeregistered {
+        operator_id: OTHER,
+        quorums: QuorumNums(vec![QuorumNum(0)]),
+    });
+    bus.publish(Event::OperatorRegistered {
+        operator_id: OTHER,
+        quorums: QuorumNums(vec![QuorumNum(2)]),
+    });
+    settle().await;
+
+    assert_eq!(gauge(&registry, "registered_stakes", 0), Some(32.0));
+    assert_eq!(gauge(&registry, "quorum_registration_status", 0), Some(1.0));
+    assert_eq!(gauge(&registry, "quorum_registration_status", 1), Some(1.0));
+    assert_eq!(gauge(&registry, "quorum_registration_status", 2), None);
+
+    bus.publish(Event::OperatorDeregistered {
+        operator_id: LOCAL,
+        quorums: QuorumNums(vec![QuorumNum(1)]),
+    });
+    settle().await;
+    assert_eq!(gauge(&registry, "quorum_registration_status", 1), Some(0.0));
+}
@@ -0,0 +1,135 @@
+use services::retry::{retry, retry_if, Backoff, RetryPolicy};
+use std::cell::Cell;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[test]
+fn exponential_delay_is_capped() {
+    let backoff = Backoff::Exponential {
+        initial: Duration::from_millis(100),
+        max: Duration::from_secs(1),
+        multiplier: 2.0,
+        jitter: 0.0,
+    };
+    assert_eq!(backoff.delay(0), Duration::from_millis(100));
+    assert_eq!(backoff.delay(3), Duration::from_millis(800));
+    assert_eq!(backoff.delay(4), Duration::from_secs(1));
+    assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(1));
+}
+
+#[test]
+fn uncapped_delay_saturates() {
+    let backoff = Backoff::Exponential {
+        initial: Duration::from_secs(1),
+        max: Duration::MAX,
+        multiplier: 10.0,
+        jitter: 0.0,
+    };
+    assert_eq!(backoff.delay(100), Duration::MAX);
+    assert_eq!(backoff.delay(u32::MAX), Duration::MAX);
+}
+
+#[test]
+fn jitter_only_shortens_delays() {
+    let backoff = Backoff::Exponential {
+        initial: Duration::from_secs(1),
+        max: Duration::from_secs(1),
+        multiplier: 1.0,
+        jitter: 0.5,
+    };
+    for _ in 0..100 {
+        let delay = backoff.delay(0);
+        assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn stops_at_max_attempts() {
+    let attempts = Cell::new(0);
+    let result: Result<(), u32> = retry(&RetryPolicy::fixed(Duration::from_secs(1), 3), || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        async move { Err(attempt) }
+    })
+    .await;
+    assert_eq!(result, Err(3));
+    assert_eq!(attempts.get(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn returns_first_success() {
+    let attempts = Cell::new(0);
+    let result = retry(&RetryPolicy::fixed(Duration::from_secs(1), 5), || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        async move {
+            if attempt < 3 {
+                Err("transient")
+            } else {
+                Ok(attempt)
+            }
+        }
+    })
+    .await;
+    assert_eq!(result, Ok(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn stops_before_overrunning_budget() {
+    let start = Instant::now();
+    let attempts = Cell::new(0);
+    let policy = RetryPolicy::budgeted(
+        Backoff::Fixed(Duration::from_secs(3)),
+        Duration::from_secs(10),
+    );
+    let result: Result<(), ()> = retry(&policy, || {
+        attempts.set(attempts.get() + 1);
+        async { Err(()) }
+    })
+    .await;
+    assert_eq!(result, Err(()));
+    // Attempts at 0s, 3s, 6s and 9s; a fifth at 12s would overrun.
+    assert_eq!(attempts.get(), 4);
+    assert_eq!(start.elapsed(), Duration::from_secs(9));
+}
+
+#[tokio::test(start_paused = true)]
+async fn uncapped_budgeted_policy_does_not_panic() {
+    let policy = RetryPolicy::budgeted(
+        Backoff::Exponential {
+            initial: Duration::from_secs(1),
+            max: Duration::MAX,
+            multiplier: 1e6,
+            jitter: 0.0,
+        },
+        Duration::from_secs(60),
+    );
+    let attempts = Cell::new(0);
+    let result: Result<(), ()> = retry(&policy, || {
+        attempts.set(attempts.get() + 1);
+        async { Err(()) }
+    })
+    .await;
+    assert_eq!(result, Err(()));
+    // 1s fits the budget, 1e6s does not.
+    assert_eq!(attempts.get(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_if_stops_on_permanent_errors() {
+    let start = Instant::now();
+    let attempts = Cell::new(0);
+    let result: Result<(), &str> = retry_if(
+        &RetryPolicy::fixed(Duration::from_secs(1), 5),
+        || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move { Err(if attempt < 2 { "transient" } else { "revert" }) }
+        },
+        |err| *err == "transient",
+    )
+    .await;
+    assert_eq!(result, Err("revert"));
+    assert_eq!(attempts.get(), 2);
+    assert_eq!(start.elapsed(), Duration::from_secs(1));
+}
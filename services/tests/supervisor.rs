@@ -0,0 +1,275 @@
+use errors::{EigenError, ErrorCategory};
+use logging::{Fields, Level, Logger, NoopLogger};
+use nodeapi::{NodeApi, NodeHealth, ServiceStatus};
+use services::retry::Backoff;
+use services::supervisor::{
+    RestartPolicy, ServiceContext, ServiceSpec, Supervisor, SupervisorError, STABLE_RUN,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn supervisor(api: &NodeApi) -> Supervisor {
+    Supervisor::new(api.clone()).with_logger(Arc::new(NoopLogger))
+}
+
+fn service<F, Fut>(id: &str, run: F) -> ServiceSpec
+where
+    F: Fn(ServiceContext) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Result<(), services::supervisor::BoxError>> + Send + 'static,
+{
+    ServiceSpec::new(id, id, "test service", run)
+}
+
+/// Marks itself ready and runs until cancelled.
+fn forever(id: &str) -> ServiceSpec {
+    service(id, |ctx| async move {
+        ctx.ready();
+        std::future::pending().await
+    })
+}
+
+fn status(api: &NodeApi, id: &str) -> ServiceStatus {
+    api.services()
+        .into_iter()
+        .find(|s| s.id == id)
+        .unwrap()
+        .status
+}
+
+fn restart_on_failure(max_restarts: u32) -> RestartPolicy {
+    RestartPolicy::OnFailure {
+        max_restarts: Some(max_restarts),
+        backoff: Backoff::Fixed(Duration::from_secs(1)),
+    }
+}
+
+/// Lets every task run until it blocks.
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+}
+
+#[derive(Default)]
+struct RecordingLogger(Mutex<Vec<(Level, String)>>);
+
+impl Logger for RecordingLogger {
+    fn log(&self, level: Level, msg: &str, _: Fields<'_>) {
+        self.0.lock().unwrap().push((level, msg.to_string()));
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn starts_services_in_dependency_order() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let started = Arc::new(Mutex::new(vec![]));
+    let recording = |id: &'static str| {
+        let started = started.clone();
+        service(id, move |ctx| {
+            started.lock().unwrap().push(id);
+            async move {
+                ctx.ready();
+                std::future::pending().await
+            }
+        })
+    };
+    let handle = supervisor(&api)
+        .with_service(recording("c").depends_on("b"))
+        .with_service(recording("b").depends_on("a"))
+        .with_service(recording("a"))
+        .start()
+        .unwrap();
+    settle().await;
+
+    assert_eq!(*started.lock().unwrap(), ["a", "b", "c"]);
+    assert_eq!(api.health(), NodeHealth::Healthy);
+
+    handle.shutdown().await;
+    for id in ["a", "b", "c"] {
+        assert_eq!(status(&api, id), ServiceStatus::Down);
+    }
+    assert_eq!(api.health(), NodeHealth::Unhealthy);
+}
+
+#[test]
+fn rejects_invalid_dependency_graphs() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let err = |sup: Supervisor| sup.start().err().unwrap();
+
+    assert_eq!(
+        err(supervisor(&api).with_service(forever("a").depends_on("missing"))),
+        SupervisorError::UnknownDependency {
+            service: "a".to_string(),
+            dependency: "missing".to_string(),
+        }
+    );
+    assert!(matches!(
+        err(supervisor(&api)
+            .with_service(forever("a").depends_on("b"))
+            .with_service(forever("b").depends_on("a"))),
+        SupervisorError::DependencyCycle(_)
+    ));
+    assert_eq!(
+        err(supervisor(&api)
+            .with_service(forever("a"))
+            .with_service(forever("a"))),
+        SupervisorError::DuplicateService("a".to_string())
+    );
+
+    let err = EigenError::from(err(supervisor(&api)
+        .with_service(forever("a"))
+        .with_service(forever("a"))));
+    assert_eq!(err.code(), "services.duplicate_service");
+    assert_eq!(err.category(), ErrorCategory::Validation);
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(SupervisorError::DuplicateService(_))
+    ));
+}
+
+#[tokio::test(start_paused = true)]
+async fn rolls_up_partial_health() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let handle = supervisor(&api)
+        .with_service(service("failing", |_| async { Err("boom".into()) }))
+        .with_service(forever("ok"))
+        .with_service(service("slow", |_| std::future::pending()))
+        .start()
+        .unwrap();
+    settle().await;
+
+    assert_eq!(status(&api, "failing"), ServiceStatus::Down);
+    assert_eq!(status(&api, "ok"), ServiceStatus::Up);
+    assert_eq!(status(&api, "slow"), ServiceStatus::Initializing);
+    assert_eq!(api.health(), NodeHealth::PartiallyHealthy);
+    handle.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn on_failure_restarts_up_to_the_limit() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let runs = Arc::new(AtomicU32::new(0));
+    let counted = runs.clone();
+    supervisor(&api)
+        .with_service(
+            service("flaky", move |_| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                async { Err("boom".into()) }
+            })
+            .restart(restart_on_failure(2)),
+        )
+        .start()
+        .unwrap()
+        .wait()
+        .await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+    assert_eq!(status(&api, "flaky"), ServiceStatus::Down);
+}
+
+#[tokio::test(start_paused = true)]
+async fn on_failure_does_not_restart_clean_exits() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let runs = Arc::new(AtomicU32::new(0));
+    let counted = runs.clone();
+    supervisor(&api)
+        .with_service(
+            service("oneshot", move |_| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                async { Ok(()) }
+            })
+            .restart(restart_on_failure(2)),
+        )
+        .start()
+        .unwrap()
+        .wait()
+        .await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn always_restarts_clean_exits() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let runs = Arc::new(AtomicU32::new(0));
+    let counted = runs.clone();
+    supervisor(&api)
+        .with_service(
+            service("loop", move |_| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                async { Ok(()) }
+            })
+            .restart(RestartPolicy::Always {
+                max_restarts: Some(2),
+                backoff: Backoff::Fixed(Duration::from_secs(1)),
+            }),
+        )
+        .start()
+        .unwrap()
+        .wait()
+        .await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn stable_runs_reset_the_restart_count() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let runs = Arc::new(AtomicU32::new(0));
+    let counted = runs.clone();
+    supervisor(&api)
+        .with_service(
+            service("daily", move |ctx| {
+                let run = counted.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    // Three long runs that each crash, then a crash loop.
+                    if run < 3 {
+                        ctx.ready();
+                        tokio::time::sleep(STABLE_RUN).await;
+                    }
+                    Err("boom".into())
+                }
+            })
+            .restart(restart_on_failure(1)),
+        )
+        .start()
+        .unwrap()
+        .wait()
+        .await;
+
+    // Each stable run earns a fresh restart, so only the crash straight after
+    // a restart hits the limit. Without the reset the second crash would.
+    assert_eq!(runs.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn dependents_of_stopped_services_go_down() {
+    let api = NodeApi::new("node", "v0.0.1");
+    let logger = Arc::new(RecordingLogger::default());
+    let app_runs = Arc::new(AtomicU32::new(0));
+    let counted = app_runs.clone();
+    Supervisor::new(api.clone())
+        .with_logger(logger.clone())
+        .with_service(service("db", |_| async {
+            Err("connection refused".into())
+        }))
+        .with_service(
+            service("app", move |_| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                std::future::pending()
+            })
+            .depends_on("db"),
+        )
+        .start()
+        .unwrap()
+        .wait()
+        .await;
+
+    assert_eq!(app_runs.load(Ordering::SeqCst), 0);
+    assert_eq!(status(&api, "app"), ServiceStatus::Down);
+    assert_eq!(api.health(), NodeHealth::Unhealthy);
+    assert!(logger
+        .0
+        .lock()
+        .unwrap()
+        .contains(&(Level::Error, "service dependency stopped".to_string())));
+}
@@ -0,0 +1,247 @@
+//! Token amounts that keep raw base units and human-readable decimals apart.
+//! Amounts serialize as decimal strings of base units, so they survive JSON
+//! parsers that round large numbers.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloy_primitives::U256;
+use core::fmt;
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const ETHER_DECIMALS: u8 = 18;
+pub const GWEI_DECIMALS: u8 = 9;
+/// Most decimals [`parse_units`] accepts, as `10^78` overflows 256 bits.
+pub const MAX_DECIMALS: u8 = 77;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AmountError {
+    #[error("{0:?} is not a decimal number")]
+    InvalidNumber(String),
+    #[error("{value:?} has more than {decimals} decimal places")]
+    TooManyDecimals { value: String, decimals: u8 },
+    #[error("{0} decimals is more than the supported {MAX_DECIMALS}")]
+    UnsupportedDecimals(u8),
+    #[error("amount overflows 256 bits")]
+    Overflow,
+    #[error("cannot combine amounts with {0} and {1} decimals")]
+    DecimalsMismatch(u8, u8),
+}
+
+#[cfg(feature = "std")]
+errors::error_codes!(AmountError {
+    InvalidNumber => ("types.invalid_amount", Validation),
+    TooManyDecimals => ("types.invalid_amount", Validation),
+    UnsupportedDecimals => ("types.unsupported_decimals", Validation),
+    Overflow => ("types.amount_overflow", Validation),
+    DecimalsMismatch => ("types.amount_decimals_mismatch", Validation),
+});
+
+/// Parses a human-readable amount such as `"1.5"` into base units, with at
+/// most [`MAX_DECIMALS`] decimals.
+pub fn parse_units(value: &str, decimals: u8) -> Result<U256, AmountError> {
+    if decimals > MAX_DECIMALS {
+        return Err(AmountError::UnsupportedDecimals(decimals));
+    }
+    let invalid = || AmountError::InvalidNumber(value.to_string());
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    let frac = frac.trim_end_matches('0');
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (int.is_empty() && frac.is_empty()) || !all_digits(int) || !all_digits(frac) {
+        return Err(invalid());
+    }
+    if frac.len() > decimals as usize {
+        return Err(AmountError::TooManyDecimals {
+            value: value.to_string(),
+            decimals,
+        });
+    }
+
+    let pow10 = |exp: usize| {
+        U256::from(10u8)
+            .checked_pow(U256::from(exp))
+            .ok_or(AmountError::Overflow)
+    };
+    let parse = |digits: &str| match digits {
+        "" => Ok(U256::ZERO),
+        _ => U256::from_str_radix(digits, 10).map_err(|_| AmountError::Overflow),
+    };
+    let int = parse(int)?
+        .checked_mul(pow10(decimals as usize)?)
+        .ok_or(AmountError::Overflow)?;
+    let frac = parse(frac)?
+        .checked_mul(pow10(decimals as usize - frac.len())?)
+        .ok_or(AmountError::Overflow)?;
+    int.checked_add(frac).ok_or(AmountError::Overflow)
+}
+
+/// Formats base units with `decimals` places, dropping trailing zeros.
+pub fn format_units(value: U256, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    let padded = format!("{digits:0>width$}", width = decimals + 1);
+    let (int, frac) = padded.split_at(padded.len() - decimals);
+    match frac.trim_end_matches('0') {
+        "" => int.to_string(),
+        frac => format!("{int}.{frac}"),
+    }
+}
+
+fn serialize_decimal<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Accepts decimal strings, and `0x`-prefixed hex as returned by RPC nodes.
+fn deserialize_decimal<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    U256::from_str(&s).map_err(serde::de::Error::custom)
+}
+
+macro_rules! u256_amount {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub U256);
+
+        impl $name {
+            pub const ZERO: Self = Self(U256::ZERO);
+
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.0.checked_add(rhs.0).map(Self)
+            }
+
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.0.checked_sub(rhs.0).map(Self)
+            }
+
+            pub fn checked_mul(self, rhs: u64) -> Option<Self> {
+                self.0.checked_mul(U256::from(rhs)).map(Self)
+            }
+
+            pub fn checked_div(self, rhs: u64) -> Option<Self> {
+                self.0.checked_div(U256::from(rhs)).map(Self)
+            }
+        }
+
+        impl From<U256> for $name {
+            fn from(value: U256) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for U256 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_decimal(&self.0, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_decimal(deserializer).map(Self)
+            }
+        }
+    };
+}
+
+u256_amount!(
+    /// An ETH amount in wei.
+    Wei
+);
+
+u256_amount!(
+    /// Strategy shares. Converting to tokens goes through the strategy's
+    /// exchange rate, so shares don't mix with token amounts.
+    Shares
+);
+
+impl Wei {
+    pub fn from_gwei(gwei: u64) -> Self {
+        Self(U256::from(gwei) * U256::from(10u64.pow(GWEI_DECIMALS as u32)))
+    }
+
+    /// Parses an ether amount such as `"0.5"`.
+    pub fn parse_ether(value: &str) -> Result<Self, AmountError> {
+        parse_units(value, ETHER_DECIMALS).map(Self)
+    }
+
+    pub fn parse_gwei(value: &str) -> Result<Self, AmountError> {
+        parse_units(value, GWEI_DECIMALS).map(Self)
+    }
+
+    pub fn format_ether(&self) -> String {
+        format_units(self.0, ETHER_DECIMALS)
+    }
+
+    pub fn format_gwei(&self) -> String {
+        format_units(self.0, GWEI_DECIMALS)
+    }
+}
+
+/// Displays in ether, e.g. `"1.5 ETH"`.
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ETH", self.format_ether())
+    }
+}
+
+impl fmt::Display for Shares {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} shares", self.0)
+    }
+}
+
+/// An ERC-20 amount in base units, with the token's `decimals()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TokenAmount {
+    #[serde(
+        serialize_with = "serialize_decimal",
+        deserialize_with = "deserialize_decimal"
+    )]
+    pub raw: U256,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: U256, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    pub fn parse(value: &str, decimals: u8) -> Result<Self, AmountError> {
+        parse_units(value, decimals).map(|raw| Self::new(raw, decimals))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, AmountError> {
+        self.same_decimals(&rhs)?;
+        let raw = self.raw.checked_add(rhs.raw).ok_or(AmountError::Overflow)?;
+        Ok(Self { raw, ..self })
+    }
+
+    /// `None` if `rhs` is larger.
+    pub fn checked_sub(self, rhs: Self) -> Result<Option<Self>, AmountError> {
+        self.same_decimals(&rhs)?;
+        Ok(self
+            .raw
+            .checked_sub(rhs.raw)
+            .map(|raw| Self { raw, ..self }))
+    }
+
+    fn same_decimals(&self, rhs: &Self) -> Result<(), AmountError> {
+        if self.decimals != rhs.decimals {
+            return Err(AmountError::DecimalsMismatch(self.decimals, rhs.decimals));
+        }
+        Ok(())
+    }
+}
+
+/// Displays in whole tokens, e.g. `"12.5"`.
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_units(self.raw, self.decimals))
+    }
+}
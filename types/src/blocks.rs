@@ -0,0 +1,157 @@
+//! Estimates between block numbers and wall-clock time, for displaying
+//! withdrawal delays, task response windows and allocation delays.
+//!
+//! Missed slots make real chains fall slightly behind these estimates, so
+//! anchor a [`BlockClock`] at a recent block when accuracy matters.
+
+use crate::BlockNumber;
+use core::fmt;
+use core::time::Duration;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockClock {
+    anchor_block: BlockNumber,
+    /// Unix timestamp of `anchor_block`, in seconds.
+    anchor_timestamp: u64,
+    block_time: Duration,
+}
+
+impl BlockClock {
+    /// # Panics
+    ///
+    /// If `block_time` is zero. Use [`BlockClock::try_new`] for block times
+    /// that come from config.
+    pub fn new(anchor_block: BlockNumber, anchor_timestamp: u64, block_time: Duration) -> Self {
+        Self::try_new(anchor_block, anchor_timestamp, block_time)
+            .expect("block time must be non-zero")
+    }
+
+    /// `None` if `block_time` is zero.
+    pub fn try_new(
+        anchor_block: BlockNumber,
+        anchor_timestamp: u64,
+        block_time: Duration,
+    ) -> Option<Self> {
+        (!block_time.is_zero()).then_some(Self {
+            anchor_block,
+            anchor_timestamp,
+            block_time,
+        })
+    }
+
+    /// A clock anchored at the merge block (mainnet) or genesis (holesky).
+    pub fn for_chain(chain_id: u64) -> Option<Self> {
+        let twelve = Duration::from_secs(12);
+        match chain_id {
+            1 => Some(Self::new(BlockNumber(15_537_394), 1_663_224_179, twelve)),
+            17000 => Some(Self::new(BlockNumber(0), 1_695_902_400, twelve)),
+            _ => None,
+        }
+    }
+
+    pub fn block_time(&self) -> Duration {
+        self.block_time
+    }
+
+    /// Estimated timestamp of `block`, saturating at the `u64` range.
+    pub fn timestamp_of(&self, block: BlockNumber) -> u64 {
+        let anchor = u128::from(self.anchor_timestamp) * NANOS_PER_SEC;
+        let nanos = if block >= self.anchor_block {
+            anchor.saturating_add(self.nanos_in(block.0 - self.anchor_block.0))
+        } else {
+            anchor.saturating_sub(self.nanos_in(self.anchor_block.0 - block.0))
+        };
+        (nanos / NANOS_PER_SEC).try_into().unwrap_or(u64::MAX)
+    }
+
+    /// Estimated latest block at `timestamp`.
+    pub fn block_at(&self, timestamp: u64) -> BlockNumber {
+        let block_nanos = self.block_time.as_nanos();
+        let nanos = |secs: u64| u128::from(secs) * NANOS_PER_SEC;
+        let saturate = |blocks: u128| u64::try_from(blocks).unwrap_or(u64::MAX);
+        if timestamp >= self.anchor_timestamp {
+            let blocks = nanos(timestamp - self.anchor_timestamp) / block_nanos;
+            BlockNumber(self.anchor_block.0.saturating_add(saturate(blocks)))
+        } else {
+            let blocks = nanos(self.anchor_timestamp - timestamp).div_ceil(block_nanos);
+            BlockNumber(self.anchor_block.0.saturating_sub(saturate(blocks)))
+        }
+    }
+
+    /// Saturates at [`Duration::MAX`].
+    pub fn duration_of(&self, blocks: u64) -> Duration {
+        let nanos = self.nanos_in(blocks);
+        match u64::try_from(nanos / NANOS_PER_SEC) {
+            Ok(secs) => Duration::new(secs, (nanos % NANOS_PER_SEC) as u32),
+            Err(_) => Duration::MAX,
+        }
+    }
+
+    /// Blocks needed for at least `duration` to pass.
+    pub fn blocks_in(&self, duration: Duration) -> u64 {
+        let nanos = self.block_time.as_nanos();
+        duration
+            .as_nanos()
+            .div_ceil(nanos)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+
+    pub fn blocks_until(&self, current: BlockNumber, target: BlockNumber) -> BlocksUntil {
+        let blocks = target.0.saturating_sub(current.0);
+        BlocksUntil {
+            blocks,
+            duration: self.duration_of(blocks),
+        }
+    }
+
+    /// Nanoseconds `blocks` take, saturating at `u128::MAX`.
+    fn nanos_in(&self, blocks: u64) -> u128 {
+        self.block_time
+            .as_nanos()
+            .saturating_mul(u128::from(blocks))
+    }
+}
+
+/// Displays as e.g. `"7200 blocks (~1d)"`, or `"now"` once reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlocksUntil {
+    pub blocks: u64,
+    pub duration: Duration,
+}
+
+impl fmt::Display for BlocksUntil {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.blocks {
+            0 => f.write_str("now"),
+            1 => write!(f, "1 block (~{})", HumanDuration(self.duration)),
+            n => write!(f, "{n} blocks (~{})", HumanDuration(self.duration)),
+        }
+    }
+}
+
+/// The two most significant units, e.g. `"2d 3h"` or `"45s"`.
+struct HumanDuration(Duration);
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let units = [
+            (secs / 86_400, "d"),
+            (secs / 3_600 % 24, "h"),
+            (secs / 60 % 60, "m"),
+            (secs % 60, "s"),
+        ];
+        let Some(first) = units.iter().position(|(n, _)| *n > 0) else {
+            return f.write_str("0s");
+        };
+        let (n, unit) = units[first];
+        write!(f, "{n}{unit}")?;
+        if let Some((n, unit)) = units.get(first + 1).filter(|(n, _)| *n > 0) {
+            write!(f, " {n}{unit}")?;
+        }
+        Ok(())
+    }
+}
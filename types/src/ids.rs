@@ -0,0 +1,149 @@
+use alloc::string::{String, ToString};
+use alloy_primitives::{hex, B256};
+use core::fmt;
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An operator's id in an AVS's registries: the hash of its BLS public key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OperatorId(pub [u8; 32]);
+
+impl OperatorId {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for OperatorId {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<OperatorId> for [u8; 32] {
+    fn from(id: OperatorId) -> Self {
+        id.0
+    }
+}
+
+impl From<B256> for OperatorId {
+    fn from(hash: B256) -> Self {
+        Self(hash.0)
+    }
+}
+
+impl From<OperatorId> for B256 {
+    fn from(id: OperatorId) -> Self {
+        B256::new(id.0)
+    }
+}
+
+impl fmt::Display for OperatorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode_prefixed(self.0))
+    }
+}
+
+impl FromStr for OperatorId {
+    type Err = hex::FromHexError;
+
+    /// Parses 32 hex-encoded bytes, with or without a `0x` prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl Serialize for OperatorId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for OperatorId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+macro_rules! numeric_newtype {
+    ($(#[$meta:meta])* $name:ident($inner:ty)) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub $inner);
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = core::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+    };
+}
+
+numeric_newtype!(
+    /// Index of a task in an AVS's task manager contract.
+    TaskIndex(u32)
+);
+
+numeric_newtype!(BlockNumber(u64));
+
+/// The `host:port` an operator advertises in the RegistryCoordinator for
+/// aggregators and other operators to reach it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Socket(pub String);
+
+impl Socket {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Socket {
+    fn from(socket: String) -> Self {
+        Self(socket)
+    }
+}
+
+impl From<&str> for Socket {
+    fn from(socket: &str) -> Self {
+        Self(socket.to_string())
+    }
+}
+
+impl From<Socket> for String {
+    fn from(socket: Socket) -> Self {
+        socket.0
+    }
+}
+
+impl fmt::Display for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,26 @@
+//! Types shared by every crate in the workspace.
+//!
+//! Without the default `std` feature the crate is `no_std` (it still needs
+//! `alloc`), for embedded verifiers that only want the primitives. That drops
+//! `Operator`, which needs URL parsing to validate, and the `errors`
+//! integration.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod amount;
+pub mod blocks;
+pub mod ids;
+#[cfg(feature = "std")]
+pub mod operator;
+pub mod quorum;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+
+pub use amount::{AmountError, Shares, TokenAmount, Wei};
+pub use blocks::{BlockClock, BlocksUntil};
+pub use ids::{BlockNumber, OperatorId, Socket, TaskIndex};
+#[cfg(feature = "std")]
+pub use operator::{Operator, OperatorError};
+pub use quorum::{QuorumError, QuorumNum, QuorumNums};
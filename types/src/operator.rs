@@ -0,0 +1,101 @@
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
+
+/// Longest URL the EigenLayer tooling accepts, for the metadata URL and the
+/// URLs inside the metadata it points to.
+pub const MAX_URL_LEN: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UrlError {
+    #[error("longer than {MAX_URL_LEN} characters")]
+    TooLong,
+    #[error("{0}")]
+    Parse(#[from] url::ParseError),
+    #[error("scheme must be http or https")]
+    Scheme,
+    #[error("host must be publicly reachable")]
+    NotPublic,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OperatorError {
+    #[error("operator address must not be the zero address")]
+    ZeroAddress,
+    #[error("metadata URL is empty")]
+    EmptyMetadataUrl,
+    #[error("invalid metadata URL: {0}")]
+    InvalidMetadataUrl(#[from] UrlError),
+}
+
+#[cfg(feature = "std")]
+errors::error_codes!(UrlError {
+    TooLong => ("types.invalid_url", Validation),
+    Parse => ("types.invalid_url", Validation),
+    Scheme => ("types.invalid_url", Validation),
+    NotPublic => ("types.invalid_url", Validation),
+});
+
+#[cfg(feature = "std")]
+errors::error_codes!(OperatorError {
+    ZeroAddress => ("types.zero_operator_address", Validation),
+    EmptyMetadataUrl => ("types.invalid_metadata_url", Validation),
+    InvalidMetadataUrl => ("types.invalid_metadata_url", Validation),
+});
+
+/// An operator's EigenLayer registration details.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Operator {
+    pub address: Address,
+    /// Zero when stakers can delegate without approval.
+    pub delegation_approver_address: Address,
+    pub metadata_url: String,
+    /// Blocks before a new allocation takes effect.
+    pub allocation_delay: u32,
+    pub staker_opt_out_window_blocks: u32,
+}
+
+impl Operator {
+    /// Checks the fields before they're sent to the DelegationManager. The
+    /// metadata URL must pass [`validate_public_url`].
+    pub fn validate(&self) -> Result<(), OperatorError> {
+        if self.address.is_zero() {
+            return Err(OperatorError::ZeroAddress);
+        }
+        if self.metadata_url.is_empty() {
+            return Err(OperatorError::EmptyMetadataUrl);
+        }
+        validate_public_url(&self.metadata_url)?;
+        Ok(())
+    }
+}
+
+/// Checks `value` is an http(s) URL of at most [`MAX_URL_LEN`] characters
+/// whose host is publicly reachable: `localhost` and loopback, private,
+/// link-local and unspecified addresses are rejected.
+pub fn validate_public_url(value: &str) -> Result<Url, UrlError> {
+    if value.len() > MAX_URL_LEN {
+        return Err(UrlError::TooLong);
+    }
+    let url = Url::parse(value)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(UrlError::Scheme);
+    }
+    let local = match url.host() {
+        Some(Host::Domain(domain)) => domain == "localhost",
+        Some(Host::Ipv4(ip)) => {
+            ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()
+        }
+        Some(Host::Ipv6(ip)) => {
+            ip.is_loopback()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                || ip.is_unspecified()
+        }
+        None => true,
+    };
+    if local {
+        return Err(UrlError::NotPublic);
+    }
+    Ok(url)
+}
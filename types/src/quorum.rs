@@ -0,0 +1,159 @@
+use alloc::vec::Vec;
+use alloy_primitives::aliases::U192;
+use alloy_primitives::U256;
+use core::fmt;
+use core::ops::Deref;
+use serde::{Deserialize, Serialize};
+
+/// `MAX_QUORUM_COUNT` in the RegistryCoordinator, which stores quorum
+/// bitmaps as `uint192`.
+pub const MAX_QUORUM_COUNT: u8 = 192;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum QuorumError {
+    #[error("quorum {0} is listed more than once")]
+    Duplicate(QuorumNum),
+    #[error("quorum {quorum} is out of range, the bitmap holds {max} quorums")]
+    OutOfRange { quorum: QuorumNum, max: usize },
+}
+
+#[cfg(feature = "std")]
+errors::error_codes!(QuorumError {
+    Duplicate => ("types.duplicate_quorum", Validation),
+    OutOfRange => ("types.quorum_out_of_range", Validation),
+});
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct QuorumNum(pub u8);
+
+impl From<u8> for QuorumNum {
+    fn from(quorum: u8) -> Self {
+        Self(quorum)
+    }
+}
+
+impl From<QuorumNum> for u8 {
+    fn from(quorum: QuorumNum) -> Self {
+        quorum.0
+    }
+}
+
+impl fmt::Display for QuorumNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An ordered list of quorums, as passed to the registry contracts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct QuorumNums(pub Vec<QuorumNum>);
+
+impl QuorumNums {
+    /// The `bytes quorumNumbers` encoding the contracts take: one byte per quorum.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.iter().map(|q| q.0).collect()
+    }
+
+    /// Encodes the quorums as a bitmap with bit `n` set for quorum `n`.
+    pub fn to_bitmap(&self) -> Result<U256, QuorumError> {
+        let mut bitmap = U256::ZERO;
+        for &quorum in &self.0 {
+            let bit = quorum.0 as usize;
+            if bitmap.bit(bit) {
+                return Err(QuorumError::Duplicate(quorum));
+            }
+            bitmap.set_bit(bit, true);
+        }
+        Ok(bitmap)
+    }
+
+    /// Like [`to_bitmap`](Self::to_bitmap), for the `uint192` bitmaps the
+    /// RegistryCoordinator stores.
+    pub fn to_bitmap_192(&self) -> Result<U192, QuorumError> {
+        if let Some(&quorum) = self.0.iter().find(|q| q.0 >= MAX_QUORUM_COUNT) {
+            return Err(QuorumError::OutOfRange {
+                quorum,
+                max: MAX_QUORUM_COUNT as usize,
+            });
+        }
+        Ok(U192::from(self.to_bitmap()?))
+    }
+
+    /// Decodes a bitmap into its quorums, in ascending order.
+    pub fn from_bitmap(bitmap: U256) -> Self {
+        (0..=u8::MAX)
+            .filter(|&quorum| bitmap.bit(quorum as usize))
+            .map(QuorumNum)
+            .collect()
+    }
+
+    pub fn from_bitmap_192(bitmap: U192) -> Self {
+        Self::from_bitmap(U256::from(bitmap))
+    }
+}
+
+impl Deref for QuorumNums {
+    type Target = [QuorumNum];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<QuorumNum>> for QuorumNums {
+    fn from(quorums: Vec<QuorumNum>) -> Self {
+        Self(quorums)
+    }
+}
+
+impl From<&[u8]> for QuorumNums {
+    fn from(bytes: &[u8]) -> Self {
+        bytes.iter().copied().map(QuorumNum).collect()
+    }
+}
+
+impl From<Vec<u8>> for QuorumNums {
+    fn from(bytes: Vec<u8>) -> Self {
+        bytes.as_slice().into()
+    }
+}
+
+impl FromIterator<QuorumNum> for QuorumNums {
+    fn from_iter<I: IntoIterator<Item = QuorumNum>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for QuorumNums {
+    type Item = QuorumNum;
+    type IntoIter = alloc::vec::IntoIter<QuorumNum>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a QuorumNums {
+    type Item = &'a QuorumNum;
+    type IntoIter = core::slice::Iter<'a, QuorumNum>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for QuorumNums {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, quorum) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            quorum.fmt(f)?;
+        }
+        Ok(())
+    }
+}
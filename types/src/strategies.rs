@@ -0,0 +1,55 @@
+//! proptest strategies for the types that end up in ABI-encoded calls.
+
+use crate::quorum::MAX_QUORUM_COUNT;
+use crate::{Operator, OperatorId, QuorumNum, QuorumNums};
+use alloy_primitives::Address;
+use proptest::prelude::*;
+
+/// Distinct quorums in any order, as a caller might list them.
+pub fn quorum_nums() -> impl Strategy<Value = QuorumNums> {
+    quorums_below(u8::MAX as u16 + 1)
+}
+
+/// Distinct quorums that fit the RegistryCoordinator's `uint192` bitmaps.
+pub fn quorum_nums_192() -> impl Strategy<Value = QuorumNums> {
+    quorums_below(MAX_QUORUM_COUNT as u16)
+}
+
+fn quorums_below(end: u16) -> impl Strategy<Value = QuorumNums> {
+    prop::collection::btree_set(0..end, 0..32)
+        .prop_map(|set| {
+            set.into_iter()
+                .map(|q| QuorumNum(q as u8))
+                .collect::<Vec<_>>()
+        })
+        .prop_shuffle()
+        .prop_map(QuorumNums)
+}
+
+pub fn operator_id() -> impl Strategy<Value = OperatorId> {
+    any::<[u8; 32]>().prop_map(OperatorId)
+}
+
+pub fn address() -> impl Strategy<Value = Address> {
+    any::<[u8; 20]>().prop_map(Address::from)
+}
+
+/// Operators that pass [`Operator::validate`].
+pub fn operator() -> impl Strategy<Value = Operator> {
+    (
+        address().prop_filter("operator address must be non-zero", |a| !a.is_zero()),
+        prop_oneof![Just(Address::ZERO), address()],
+        "[a-z]{1,20}\\.(com|xyz|io)/[a-z0-9_-]{0,30}\\.json",
+        any::<u32>(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(address, delegation_approver_address, path, allocation_delay, opt_out)| Operator {
+                address,
+                delegation_approver_address,
+                metadata_url: format!("https://{path}"),
+                allocation_delay,
+                staker_opt_out_window_blocks: opt_out,
+            },
+        )
+}
@@ -0,0 +1,153 @@
+use alloy_primitives::U256;
+use errors::{EigenError, ErrorCategory};
+use proptest::prelude::*;
+use types::amount::{format_units, parse_units, MAX_DECIMALS};
+use types::{AmountError, Shares, TokenAmount, Wei};
+
+fn wei(value: &str) -> U256 {
+    value.parse().unwrap()
+}
+
+#[test]
+fn parses_units() {
+    assert_eq!(parse_units("1", 18), Ok(wei("1000000000000000000")));
+    assert_eq!(parse_units("1.5", 18), Ok(wei("1500000000000000000")));
+    assert_eq!(parse_units("0.000000000000000001", 18), Ok(wei("1")));
+    assert_eq!(parse_units("1.", 18), Ok(wei("1000000000000000000")));
+    assert_eq!(parse_units(".5", 1), Ok(wei("5")));
+    assert_eq!(parse_units("007", 0), Ok(wei("7")));
+    // Trailing zeros don't count towards the decimal places.
+    assert_eq!(parse_units("1.2300", 2), Ok(wei("123")));
+}
+
+#[test]
+fn rejects_malformed_numbers() {
+    for value in [
+        "", ".", "-1", "+1", " 1", "1 ", "1,5", "1.2.3", "1e18", "0x10",
+    ] {
+        assert_eq!(
+            parse_units(value, 18),
+            Err(AmountError::InvalidNumber(value.to_string())),
+            "{value:?}"
+        );
+    }
+
+    let err = EigenError::from(parse_units("1e18", 18).unwrap_err());
+    assert_eq!(err.code(), "types.invalid_amount");
+    assert_eq!(err.category(), ErrorCategory::Validation);
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(AmountError::InvalidNumber(_))
+    ));
+}
+
+#[test]
+fn rejects_too_many_decimals() {
+    assert_eq!(
+        parse_units("0.0000000000000000001", 18),
+        Err(AmountError::TooManyDecimals {
+            value: "0.0000000000000000001".to_string(),
+            decimals: 18,
+        })
+    );
+    assert!(matches!(
+        parse_units("1.5", 0),
+        Err(AmountError::TooManyDecimals { decimals: 0, .. })
+    ));
+}
+
+#[test]
+fn rejects_overflow() {
+    let max = U256::MAX.to_string();
+    assert_eq!(parse_units(&max, 0), Ok(U256::MAX));
+    assert_eq!(
+        parse_units(&format!("{max}0"), 0),
+        Err(AmountError::Overflow)
+    );
+    assert_eq!(parse_units(&max, 1), Err(AmountError::Overflow));
+    assert_eq!(
+        parse_units(&format!("1{}", "0".repeat(39)), 39),
+        Err(AmountError::Overflow)
+    );
+}
+
+#[test]
+fn rejects_unsupported_decimals() {
+    assert_eq!(
+        parse_units("1", MAX_DECIMALS),
+        Ok(U256::from(10u8).pow(U256::from(MAX_DECIMALS)))
+    );
+    for value in ["0", "1"] {
+        assert_eq!(
+            parse_units(value, 78),
+            Err(AmountError::UnsupportedDecimals(78))
+        );
+    }
+    assert_eq!(
+        parse_units("0", u8::MAX),
+        Err(AmountError::UnsupportedDecimals(u8::MAX))
+    );
+}
+
+#[test]
+fn formats_units() {
+    assert_eq!(format_units(U256::ZERO, 18), "0");
+    assert_eq!(format_units(wei("1"), 18), "0.000000000000000001");
+    assert_eq!(format_units(wei("1500000000000000000"), 18), "1.5");
+    assert_eq!(format_units(wei("2000000000000000000"), 18), "2");
+    assert_eq!(format_units(wei("123"), 0), "123");
+    assert_eq!(format_units(wei("123"), 2), "1.23");
+}
+
+#[test]
+fn wei_helpers() {
+    assert_eq!(Wei::from_gwei(1), Wei(wei("1000000000")));
+    assert_eq!(Wei::parse_gwei("1.5").unwrap(), Wei(wei("1500000000")));
+    assert_eq!(Wei::parse_ether("0.5").unwrap().to_string(), "0.5 ETH");
+    assert_eq!(Wei(U256::MAX).checked_add(Wei(wei("1"))), None);
+    assert_eq!(Wei::ZERO.checked_sub(Wei(wei("1"))), None);
+    assert_eq!(Wei(wei("10")).checked_div(0), None);
+    assert_eq!(Shares(wei("3")).checked_mul(2), Some(Shares(wei("6"))));
+}
+
+#[test]
+fn serializes_as_decimal_strings() {
+    let amount = Wei(wei("1000000000000000000000"));
+    let json = serde_json::to_string(&amount).unwrap();
+    assert_eq!(json, r#""1000000000000000000000""#);
+    assert_eq!(serde_json::from_str::<Wei>(&json).unwrap(), amount);
+    assert_eq!(
+        serde_json::from_str::<Wei>(r#""0x10""#).unwrap(),
+        Wei(wei("16"))
+    );
+    assert!(serde_json::from_str::<Wei>("16").is_err());
+
+    let token = TokenAmount::parse("12.5", 6).unwrap();
+    let json = serde_json::to_string(&token).unwrap();
+    assert_eq!(json, r#"{"raw":"12500000","decimals":6}"#);
+    assert_eq!(serde_json::from_str::<TokenAmount>(&json).unwrap(), token);
+}
+
+#[test]
+fn token_amounts_must_share_decimals() {
+    let usdc = TokenAmount::parse("1", 6).unwrap();
+    let dai = TokenAmount::parse("1", 18).unwrap();
+    assert_eq!(
+        usdc.checked_add(dai),
+        Err(AmountError::DecimalsMismatch(6, 18))
+    );
+    assert_eq!(
+        usdc.checked_sub(dai),
+        Err(AmountError::DecimalsMismatch(6, 18))
+    );
+    assert_eq!(usdc.checked_add(usdc).unwrap().to_string(), "2");
+    assert_eq!(usdc.checked_sub(usdc.checked_add(usdc).unwrap()), Ok(None));
+}
+
+proptest! {
+    #[test]
+    fn format_parse_round_trips(limbs in any::<[u64; 4]>(), decimals in 0..=MAX_DECIMALS) {
+        let value = U256::from_limbs(limbs);
+        prop_assert_eq!(parse_units(&format_units(value, decimals), decimals), Ok(value));
+    }
+}
@@ -0,0 +1,134 @@
+use core::time::Duration;
+use proptest::prelude::*;
+use types::{BlockClock, BlockNumber};
+
+const ANCHOR: u64 = 1_700_000_000;
+
+fn clock(block_time: Duration) -> BlockClock {
+    BlockClock::new(BlockNumber(1_000), ANCHOR, block_time)
+}
+
+#[test]
+fn converts_at_twelve_seconds() {
+    let clock = clock(Duration::from_secs(12));
+    assert_eq!(clock.timestamp_of(BlockNumber(1_000)), ANCHOR);
+    assert_eq!(clock.timestamp_of(BlockNumber(1_010)), ANCHOR + 120);
+    assert_eq!(clock.timestamp_of(BlockNumber(990)), ANCHOR - 120);
+    assert_eq!(clock.block_at(ANCHOR + 131), BlockNumber(1_010));
+    assert_eq!(clock.block_at(ANCHOR - 1), BlockNumber(999));
+    assert_eq!(clock.block_at(ANCHOR - 12), BlockNumber(999));
+    assert_eq!(clock.block_at(ANCHOR - 13), BlockNumber(998));
+}
+
+#[test]
+fn rejects_zero_block_times() {
+    assert_eq!(
+        BlockClock::try_new(BlockNumber(1_000), ANCHOR, Duration::ZERO),
+        None
+    );
+    assert_eq!(
+        BlockClock::try_new(BlockNumber(1_000), ANCHOR, Duration::from_secs(12)),
+        Some(clock(Duration::from_secs(12)))
+    );
+}
+
+#[test]
+#[should_panic(expected = "block time must be non-zero")]
+fn new_panics_on_zero_block_time() {
+    clock(Duration::ZERO);
+}
+
+#[test]
+fn handles_fractional_block_times() {
+    let clock = clock(Duration::from_millis(2_500));
+    assert_eq!(clock.timestamp_of(BlockNumber(1_100)), ANCHOR + 250);
+    assert_eq!(clock.timestamp_of(BlockNumber(1_001)), ANCHOR + 2);
+    assert_eq!(clock.timestamp_of(BlockNumber(999)), ANCHOR - 3);
+    assert_eq!(clock.block_at(ANCHOR + 250), BlockNumber(1_100));
+    assert_eq!(clock.duration_of(3), Duration::from_millis(7_500));
+}
+
+#[test]
+fn handles_sub_second_block_times() {
+    let clock = clock(Duration::from_millis(250));
+    assert_eq!(clock.timestamp_of(BlockNumber(1_010)), ANCHOR + 2);
+    assert_eq!(clock.block_at(ANCHOR + 1), BlockNumber(1_004));
+    assert_eq!(clock.block_at(ANCHOR - 1), BlockNumber(996));
+    assert_eq!(clock.blocks_in(Duration::from_secs(1)), 4);
+}
+
+#[test]
+fn saturates_instead_of_overflowing() {
+    let slow = clock(Duration::from_secs(u64::MAX));
+    assert_eq!(slow.timestamp_of(BlockNumber(u64::MAX)), u64::MAX);
+    assert_eq!(slow.timestamp_of(BlockNumber(0)), 0);
+    assert_eq!(slow.duration_of(u64::MAX), Duration::MAX);
+
+    let fast = clock(Duration::from_nanos(1));
+    assert_eq!(fast.block_at(u64::MAX), BlockNumber(u64::MAX));
+    assert_eq!(fast.block_at(0), BlockNumber(0));
+    assert_eq!(fast.duration_of(u64::MAX), Duration::from_nanos(u64::MAX));
+}
+
+#[test]
+fn blocks_in_rounds_up() {
+    let clock = clock(Duration::from_secs(12));
+    assert_eq!(clock.blocks_in(Duration::ZERO), 0);
+    assert_eq!(clock.blocks_in(Duration::from_secs(12)), 1);
+    assert_eq!(clock.blocks_in(Duration::from_secs(13)), 2);
+    assert_eq!(clock.blocks_in(Duration::from_secs(86_400)), 7_200);
+}
+
+#[test]
+fn formats_blocks_until() {
+    let clock = clock(Duration::from_secs(12));
+    let until = |blocks| {
+        clock
+            .blocks_until(BlockNumber(0), BlockNumber(blocks))
+            .to_string()
+    };
+    assert_eq!(until(0), "now");
+    assert_eq!(until(1), "1 block (~12s)");
+    assert_eq!(until(7_200), "7200 blocks (~1d)");
+    assert_eq!(until(7_500), "7500 blocks (~1d 1h)");
+    assert_eq!(
+        clock
+            .blocks_until(BlockNumber(10), BlockNumber(5))
+            .to_string(),
+        "now"
+    );
+}
+
+#[test]
+fn knows_mainnet_and_holesky() {
+    let mainnet = BlockClock::for_chain(1).unwrap();
+    assert_eq!(mainnet.timestamp_of(BlockNumber(15_537_394)), 1_663_224_179);
+    assert_eq!(
+        BlockClock::for_chain(17000).unwrap().block_time(),
+        Duration::from_secs(12)
+    );
+    assert_eq!(BlockClock::for_chain(5), None);
+}
+
+proptest! {
+    #[test]
+    fn whole_second_clocks_round_trip(
+        secs in 1u64..=60,
+        offset in 0u64..1_000_000_000,
+    ) {
+        let clock = clock(Duration::from_secs(secs));
+        let block = BlockNumber(1_000 + offset);
+        prop_assert_eq!(clock.block_at(clock.timestamp_of(block)), block);
+    }
+
+    #[test]
+    fn block_at_is_monotonic(
+        millis in 1u64..=60_000,
+        a in any::<u64>(),
+        b in any::<u64>(),
+    ) {
+        let clock = clock(Duration::from_millis(millis));
+        let (lo, hi) = (a.min(b), a.max(b));
+        prop_assert!(clock.block_at(lo) <= clock.block_at(hi));
+    }
+}
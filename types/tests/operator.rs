@@ -0,0 +1,64 @@
+use alloy_primitives::Address;
+use types::operator::{validate_public_url, UrlError, MAX_URL_LEN};
+use types::{Operator, OperatorError};
+
+fn operator(metadata_url: &str) -> Operator {
+    Operator {
+        address: Address::repeat_byte(1),
+        metadata_url: metadata_url.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn accepts_public_urls() {
+    for url in [
+        "https://example.com/metadata.json",
+        "http://93.184.216.34/metadata.json",
+        "https://[2606:2800:220:1::]/metadata.json",
+    ] {
+        validate_public_url(url).unwrap();
+        operator(url).validate().unwrap();
+    }
+}
+
+#[test]
+fn rejects_unreachable_hosts() {
+    for url in [
+        "http://localhost:8080/",
+        "http://127.0.0.1/",
+        "http://0.0.0.0/",
+        "http://10.0.0.1/",
+        "http://172.16.0.1/",
+        "http://192.168.1.10/",
+        "http://169.254.169.254/",
+        "http://[::1]/",
+        "http://[fd00::1]/",
+        "http://[fe80::1]/",
+    ] {
+        assert_eq!(validate_public_url(url), Err(UrlError::NotPublic), "{url}");
+        assert_eq!(
+            operator(url).validate(),
+            Err(OperatorError::InvalidMetadataUrl(UrlError::NotPublic)),
+            "{url}"
+        );
+    }
+}
+
+#[test]
+fn rejects_malformed_urls() {
+    assert_eq!(
+        validate_public_url("ftp://example.com/"),
+        Err(UrlError::Scheme)
+    );
+    assert!(matches!(
+        validate_public_url("example.com"),
+        Err(UrlError::Parse(_))
+    ));
+    let long = format!("https://example.com/{}", "a".repeat(MAX_URL_LEN));
+    assert_eq!(validate_public_url(&long), Err(UrlError::TooLong));
+    assert_eq!(
+        operator("").validate(),
+        Err(OperatorError::EmptyMetadataUrl)
+    );
+}
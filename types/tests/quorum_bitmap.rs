@@ -0,0 +1,72 @@
+use alloy_primitives::aliases::U192;
+use alloy_primitives::U256;
+use proptest::prelude::*;
+use std::collections::BTreeSet;
+use types::quorum::MAX_QUORUM_COUNT;
+use types::strategies::quorum_nums_192;
+use types::{QuorumError, QuorumNum, QuorumNums};
+
+fn quorums(set: &BTreeSet<u8>) -> QuorumNums {
+    set.iter().copied().map(QuorumNum).collect()
+}
+
+proptest! {
+    #[test]
+    fn bitmap_round_trips(set in prop::collection::btree_set(any::<u8>(), 0..64)) {
+        let bitmap = quorums(&set).to_bitmap().unwrap();
+        prop_assert_eq!(bitmap.count_ones(), set.len());
+        prop_assert_eq!(QuorumNums::from_bitmap(bitmap), quorums(&set));
+    }
+
+    #[test]
+    fn bitmap_decode_encode(limbs in any::<[u64; 4]>()) {
+        let bitmap = U256::from_limbs(limbs);
+        prop_assert_eq!(QuorumNums::from_bitmap(bitmap).to_bitmap().unwrap(), bitmap);
+    }
+
+    #[test]
+    fn bitmap_ignores_order(set in prop::collection::btree_set(any::<u8>(), 0..64)) {
+        let reversed: QuorumNums = set.iter().rev().copied().map(QuorumNum).collect();
+        prop_assert_eq!(reversed.to_bitmap().unwrap(), quorums(&set).to_bitmap().unwrap());
+    }
+
+    #[test]
+    fn duplicates_are_rejected(
+        set in prop::collection::btree_set(any::<u8>(), 1..64),
+        index in any::<prop::sample::Index>(),
+    ) {
+        let mut list: Vec<QuorumNum> = quorums(&set).to_vec();
+        let duplicate = *index.get(&list);
+        list.push(duplicate);
+        prop_assert_eq!(
+            QuorumNums::from(list).to_bitmap(),
+            Err(QuorumError::Duplicate(duplicate))
+        );
+    }
+
+    #[test]
+    fn bitmap_192_round_trips(list in quorum_nums_192()) {
+        let set: BTreeSet<u8> = list.0.iter().map(|q| q.0).collect();
+        let bitmap = list.to_bitmap_192().unwrap();
+        prop_assert_eq!(QuorumNums::from_bitmap_192(bitmap), quorums(&set));
+    }
+
+    #[test]
+    fn bitmap_192_rejects_out_of_range(
+        set in prop::collection::btree_set(0..MAX_QUORUM_COUNT, 0..64),
+        quorum in MAX_QUORUM_COUNT..=u8::MAX,
+    ) {
+        let mut list = quorums(&set).to_vec();
+        list.push(QuorumNum(quorum));
+        prop_assert_eq!(
+            QuorumNums::from(list).to_bitmap_192(),
+            Err(QuorumError::OutOfRange { quorum: QuorumNum(quorum), max: 192 })
+        );
+    }
+}
+
+#[test]
+fn bitmap_bits_match_quorum_numbers() {
+    let bitmap = QuorumNums::from(vec![0u8, 1, 191]).to_bitmap_192().unwrap();
+    assert_eq!(bitmap, U192::from(0b11u8) | (U192::from(1u8) << 191));
+}
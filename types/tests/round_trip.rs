@@ -0,0 +1,37 @@
+use alloy_primitives::Bytes;
+use alloy_sol_types::SolValue;
+use proptest::prelude::*;
+use types::strategies::{operator, operator_id, quorum_nums};
+use types::{Operator, OperatorId, QuorumNums};
+
+proptest! {
+    #[test]
+    fn quorum_bytes_round_trip(quorums in quorum_nums()) {
+        prop_assert_eq!(QuorumNums::from(quorums.to_bytes()), quorums);
+    }
+
+    #[test]
+    fn quorum_bytes_abi_round_trip(quorums in quorum_nums()) {
+        let encoded = Bytes::from(quorums.to_bytes()).abi_encode();
+        let decoded = Bytes::abi_decode(&encoded).unwrap();
+        prop_assert_eq!(QuorumNums::from(decoded.to_vec()), quorums);
+    }
+
+    #[test]
+    fn operator_id_round_trips(id in operator_id()) {
+        prop_assert_eq!(id.to_string().parse::<OperatorId>().unwrap(), id);
+        let json = serde_json::to_string(&id).unwrap();
+        prop_assert_eq!(serde_json::from_str::<OperatorId>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn generated_operators_validate(operator in operator()) {
+        prop_assert_eq!(operator.validate(), Ok(()));
+    }
+
+    #[test]
+    fn operator_serde_round_trips(operator in operator()) {
+        let json = serde_json::to_string(&operator).unwrap();
+        prop_assert_eq!(serde_json::from_str::<Operator>(&json).unwrap(), operator);
+    }
+}